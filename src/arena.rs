@@ -0,0 +1,196 @@
+use core::{
+    cell::Cell,
+    fmt::{self, Debug},
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr::{self, NonNull},
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{tailvec::slice_assume_init_mut, TailVec, VecLike};
+
+/// Append-only arena over [`TailVec`], create from [`into_arena`]
+///
+/// [`alloc`] takes `&self`, the pushed elements never move,
+/// so references of earlier elements can be held while pushing more.
+///
+/// [`alloc`] returns `&mut T` tied to the `&self` borrow, not `&'a mut T`,
+/// so the elements are accessible by `&mut self` and [`into_tail`] only,
+/// after all references returned by [`alloc`] are dead.
+///
+/// The allocated elements are kept when [`ArenaTail`] is dropped,
+/// or [`into_tail`].
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec![0];
+/// vec.reserve_exact(3);
+/// let (_, rest) = vec.split_tail(1);
+/// let arena = rest.into_arena();
+///
+/// let a = arena.alloc(1).unwrap();
+/// let b = arena.alloc(2).unwrap();
+/// *a += *b; // Both are borrowed
+/// let c = arena.alloc(*a + *b).unwrap();
+/// assert_eq!(arena.alloc(4), Err(4)); // Overflow of capacity
+/// assert_eq!((*a, *b, *c), (3, 2, 5));
+/// assert_eq!(arena.len(), 3);
+/// drop(arena);
+///
+/// assert_eq!(vec, [0, 3, 2, 5]);
+/// ```
+///
+/// [`into_arena`]: TailVec::into_arena
+/// [`alloc`]: ArenaTail::alloc
+/// [`into_tail`]: ArenaTail::into_tail
+pub struct ArenaTail<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    tail: TailVec<'a, T, V>,
+    buf: NonNull<[MaybeUninit<T>]>,
+    len: Cell<usize>,
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into append-only arena, the elements are kept
+    ///
+    /// *See [`ArenaTail`] for more documents*
+    #[must_use]
+    pub fn into_arena(mut self) -> ArenaTail<'a, T, V> {
+        let len = self.len();
+        let buf = unsafe {
+            self.set_len(0);
+            NonNull::from(self.parts())
+        };
+        ArenaTail { tail: self, buf, len: Cell::new(len) }
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> ArenaTail<'a, T, V> {
+    /// Into [`TailVec`], the allocated elements are kept
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let mut tail = ptr::read(&this.tail);
+            tail.set_len(this.len.get());
+            tail
+        }
+    }
+
+    /// Elements count of the arena
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if the arena contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Capacity of the arena, equal [`TailVec::capacity`]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Push a value by shared reference, and return its mutable reference
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: ArenaTail::len
+    /// [`capacity()`]: ArenaTail::capacity
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc(&self, value: T) -> Result<&mut T, T> {
+        let len = self.len.get();
+        if len == self.capacity() {
+            return Err(value);
+        }
+        self.len.set(len + 1);
+        // each slot is written only once, and never accessed by `&self` else
+        unsafe {
+            let slot = self.buf.cast::<MaybeUninit<T>>().add(len);
+            Ok((*slot.as_ptr()).write(value))
+        }
+    }
+
+    /// Push all values of `iter` by shared reference, and return them
+    ///
+    /// # Errors
+    /// - [`Err`] with the first element that cannot be pushed,
+    ///   then the elements before it are kept in the arena
+    ///   and the elements after it are not consumed.
+    ///
+    /// # Panics
+    /// - `iter` allocates in this arena, the returned slice would alias it
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(4);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let arena = rest.into_arena();
+    ///
+    /// let head = arena.alloc(0).unwrap();
+    /// let values = arena.alloc_extend([1, 2]).unwrap();
+    /// assert_eq!(values, [1, 2]);
+    /// assert_eq!(arena.alloc_extend([3, 4]), Err(4));
+    /// assert_eq!(*head, 0);
+    ///
+    /// drop(arena);
+    /// assert_eq!(vec, [0, 1, 2, 3]);
+    /// ```
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    pub fn alloc_extend<I>(&self, iter: I) -> Result<&mut [T], T>
+    where I: IntoIterator<Item = T>,
+    {
+        #[track_caller]
+        fn check_reentry(arena_len: usize, expected: usize) {
+            assert!(arena_len == expected, "arena is allocated by the iterator of alloc_extend");
+        }
+
+        let start = self.len();
+        let mut len = 0;
+        for value in iter {
+            check_reentry(self.len(), start + len);
+            self.alloc(value)?;
+            len += 1;
+        }
+        check_reentry(self.len(), start + len);
+        unsafe {
+            let ptr = self.buf.cast::<T>().add(start);
+            Ok(NonNull::slice_from_raw_parts(ptr, len).as_mut())
+        }
+    }
+
+    /// Get all elements
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        let len = self.len();
+        unsafe { slice_assume_init_mut(&mut self.buf.as_mut()[..len]) }
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Drop for ArenaTail<'_, T, V> {
+    fn drop(&mut self) {
+        unsafe { self.tail.set_len(self.len.get()) }
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Debug for ArenaTail<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaTail")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish_non_exhaustive()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + ?Sized + Send> Send for ArenaTail<'_, T, V> {
+}
@@ -0,0 +1,184 @@
+//! [`VecLike`] and [`InlineVec`] implementations of third-party vectors
+//!
+//! The slots of third-party inline vectors are inside themselves,
+//! and their length fields are private, so they are split through [`InlineStorage`].
+//!
+//! [`InlineVec`]: crate::InlineVec
+//! [`InlineStorage`]: crate::InlineStorage
+
+#[cfg(feature = "bumpalo")]
+use core::{mem::MaybeUninit, ops::RangeBounds, slice};
+
+#[cfg(any(feature = "arrayvec", feature = "heapless", feature = "smallvec"))]
+use crate::InlineVec;
+#[cfg(feature = "bumpalo")]
+use crate::{PushPop, VecLike};
+
+#[cfg(feature = "arrayvec")]
+unsafe impl<T, const CAP: usize> InlineVec for arrayvec::ArrayVec<T, CAP> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        CAP
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+unsafe impl<T, const N: usize, LenT> InlineVec for heapless::Vec<T, N, LenT>
+where LenT: heapless::LenType,
+{
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn as_ptr(&self) -> *const T {
+        self.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+}
+
+/// Split of inline [`smallvec::SmallVec`] is valid,
+/// because [`InlineStorage`] never grows, it cannot spill
+///
+/// [`InlineStorage`]: crate::InlineStorage
+#[cfg(feature = "smallvec")]
+unsafe impl<A: smallvec::Array> InlineVec for smallvec::SmallVec<A> {
+    type T = A::Item;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn as_ptr(&self) -> *const A::Item {
+        self.as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut A::Item {
+        self.as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+}
+
+#[cfg(feature = "bumpalo")]
+unsafe impl<T> VecLike for bumpalo::collections::Vec<'_, T> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        let len = self.len();
+        let cap = self.capacity();
+        unsafe {
+            let ptr = self.as_mut_ptr().add(len);
+            slice::from_raw_parts_mut(ptr.cast(), cap - len)
+        }
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+}
+
+/// [`bumpalo::collections::Vec`] grows in the arena, it is never full
+#[cfg(feature = "bumpalo")]
+impl<'bump, T> PushPop for bumpalo::collections::Vec<'bump, T> {
+    type Drain<'a> = bumpalo::collections::vec::Drain<'a, 'bump, T>
+    where Self: 'a;
+
+    fn try_push(&mut self, value: T) -> Result<(), T> {
+        self.push(value);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn remaining_capacity(&self) -> usize {
+        usize::MAX - self.len()
+    }
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        self.drain(range)
+    }
+}
+
+/// [`TailVec`] of [`bumpalo::collections::Vec`]
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// use bumpalo::{Bump, collections::Vec};
+///
+/// fn push_one(tail: &mut BumpTailVec<'_, '_, i32>) {
+///     tail.push(1).unwrap();
+/// }
+///
+/// let bump = Bump::new();
+/// let mut vec = Vec::with_capacity_in(2, &bump);
+/// vec.push(0);
+/// let (_, mut rest) = vec.split_tail(1);
+/// push_one(&mut rest);
+/// drop(rest);
+/// assert_eq!(vec, [0, 1]);
+/// ```
+///
+/// [`TailVec`]: crate::TailVec
+#[cfg(feature = "bumpalo")]
+pub type BumpTailVec<'a, 'bump, T> = crate::TailVec<'a, T, bumpalo::collections::Vec<'bump, T>>;
@@ -0,0 +1,111 @@
+use core::{
+    fmt::{self, Debug},
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+};
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::VecDeque, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::VecDeque;
+
+use crate::VecLike;
+
+/// [`VecDeque`] contiguous adapter of [`VecLike`]
+///
+/// Make the ring buffer contiguous at the start of the allocation,
+/// then it can use [`split_tail`], the [`TailVec`] pushes into the free space.
+///
+/// The elements are put back to [`VecDeque`] when [`DequeVec`] is dropped.
+///
+/// This never reallocates, but it may move elements in `O(n)`,
+/// if the ring buffer is not at the start of the allocation
+///
+/// # Leaking
+/// If the [`DequeVec`] goes out of scope without being dropped
+/// (due to [`mem::forget`], for example),
+/// the [`VecDeque`] will be empty and the elements are leaked.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// use std::collections::VecDeque;
+///
+/// let mut deque = VecDeque::with_capacity(5);
+/// deque.extend([2, 3]);
+/// deque.push_front(1);
+///
+/// let mut vec = DequeVec::new(&mut deque);
+/// let (left, mut rest) = vec.split_tail(1);
+/// assert_eq!(left, &mut [1]);
+/// assert_eq!(rest, &mut [2, 3]);
+/// rest.push(4).unwrap();
+/// drop(rest);
+/// drop(vec);
+///
+/// assert_eq!(deque, [1, 2, 3, 4]);
+/// ```
+///
+/// [`split_tail`]: crate::SplitTail::split_tail
+/// [`TailVec`]: crate::TailVec
+/// [`mem::forget`]: core::mem::forget
+pub struct DequeVec<'a, T> {
+    deque: &'a mut VecDeque<T>,
+    vec: Vec<T>,
+}
+impl<'a, T> DequeVec<'a, T> {
+    /// Take elements of [`VecDeque`] into contiguous adapter
+    pub fn new(deque: &'a mut VecDeque<T>) -> Self {
+        let vec = mem::take(deque).into();
+        Self { deque, vec }
+    }
+}
+impl<'a, T> From<&'a mut VecDeque<T>> for DequeVec<'a, T> {
+    fn from(deque: &'a mut VecDeque<T>) -> Self {
+        Self::new(deque)
+    }
+}
+impl<T> Drop for DequeVec<'_, T> {
+    fn drop(&mut self) {
+        *self.deque = mem::take(&mut self.vec).into();
+    }
+}
+impl<T> Deref for DequeVec<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+impl<T> DerefMut for DequeVec<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+impl<T: Debug> Debug for DequeVec<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.vec.fmt(f)
+    }
+}
+unsafe impl<T> VecLike for DequeVec<'_, T> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        self.vec.spare_capacity_mut()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.vec.set_len(new_len);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 use core::{
     fmt::{self, Debug},
     iter::FusedIterator,
+    marker::PhantomData,
     mem,
     ops::{Range, RangeBounds},
     ptr::{self, NonNull},
@@ -11,7 +12,7 @@ use core::{
 
 use crate::{utils, TailVec, VecLike};
 
-impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
     /// Removes the specified range from the vector in bulk, returning all
     /// removed elements as an iterator. If the iterator is dropped before
     /// being fully consumed, it drops the remaining removed elements.
@@ -50,55 +51,53 @@ impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
     pub fn drain<R>(&mut self, range: R) -> Drain<'_, V>
     where R: RangeBounds<usize>,
     {
-        let len = self.len();
-        let Range { start, end } = utils::range(range, ..len);
-
-        unsafe {
-            self.set_len(start);
-
-            let ptr = self.as_ptr().add(start);
-            let slice = slice::from_raw_parts(ptr, end - start);
+        Drain::new(self, range)
+    }
+}
 
-            Drain {
-                tail_start: end,
-                tail_len: len - end,
-                iter: slice.iter(),
-                vec: NonNull::from(self),
-            }
-        }
+/// Object safe [`VecLike::set_len`], the drained vec of [`Drain`]
+trait SetLen {
+    unsafe fn set_len(&mut self, new_len: usize);
+}
+impl<S: VecLike + ?Sized> SetLen for S {
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe { VecLike::set_len(self, new_len) }
     }
 }
 
-struct DropGuard<'r, 'a, V: VecLike>(&'r mut Drain<'a, V>);
-impl<V: VecLike> Drop for DropGuard<'_, '_, V> {
+struct DropGuard<'r, 'a, V: VecLike + ?Sized>(&'r mut Drain<'a, V>);
+impl<V: VecLike + ?Sized> Drop for DropGuard<'_, '_, V> {
     fn drop(&mut self) {
         // a a a a a d d d i i r r r r r
         //           ^     ^   ^
-        // src_vec.len() iter tail_start
-        // start              tail
+        //         start  iter tail_start
+        //                     tail
         unsafe {
-            let src_vec = self.0.vec.as_mut();
-            let start = src_vec.len();
+            let base = self.0.base;
+            let start = self.0.start;
             let tail = self.0.tail_start;
             let count = self.0.tail_len;
 
             if tail != start {
-                let src = src_vec.as_ptr().add(tail);
-                let dst = src_vec.as_mut_ptr().add(start);
+                let src = base.add(tail);
+                let dst = base.add(start);
                 ptr::copy(src, dst, count);
             }
 
-            src_vec.set_len(start + count);
+            self.0.vec.as_mut().set_len(start + count);
         }
     }
 }
 
-/// A draining iterator for [`TailVec`]
+/// A draining iterator for [`TailVec`] and fixed-capacity storages
 ///
-/// This struct is created by [`TailVec::drain`].
+/// This struct is created by [`TailVec::drain`] or [`PushPop::drain`],
+/// `V` is the vec of [`TailVec`], or the storage itself.
 ///
 /// See its documentation for more.
 ///
+/// [`PushPop::drain`]: crate::PushPop::drain
+///
 /// # Examples
 ///
 /// ```
@@ -106,13 +105,55 @@ impl<V: VecLike> Drop for DropGuard<'_, '_, V> {
 /// let mut vec = vec!['a', 'b', 'c'];
 /// let (_, mut rvec) = vec.split_tail(0);
 /// let iter = rvec.drain(..);
-pub struct Drain<'a, V: VecLike> where V::T: 'a {
+pub struct Drain<'a, V: VecLike + ?Sized> where V::T: 'a {
+    start: usize,
     tail_start: usize,
     tail_len: usize,
     iter: slice::Iter<'a, V::T>,
-    vec: NonNull<TailVec<'a, V::T, V>>,
+    base: *mut V::T,
+    vec: NonNull<dyn SetLen + 'a>,
+    _phantom: PhantomData<&'a mut V>,
+}
+impl<'a, V: VecLike + ?Sized> Drain<'a, V> {
+    /// Create draining iterator of any [`VecLike`]
+    ///
+    /// The length of `vec` is `start` until [`Drain`] dropped,
+    /// or zero if it has no length pointer, see [`VecLike::spare_capacity_and_len_mut`]
+    #[track_caller]
+    pub(crate) fn new<S, R>(vec: &'a mut S, range: R) -> Self
+    where S: VecLike<T = V::T> + 'a,
+          R: RangeBounds<usize>,
+    {
+        let len = vec.len();
+        let Range { start, end } = utils::range(range, ..len);
+
+        unsafe {
+            let mut vec = NonNull::from(vec);
+            // Do not access `vec` again before drop, it may contain the data inline
+            let base = {
+                let vec = vec.as_mut();
+                vec.set_len(0);
+                let (spare, len_ptr) = vec.spare_capacity_and_len_mut();
+                if let Some(len_ptr) = len_ptr {
+                    len_ptr.write(start);
+                }
+                spare.as_mut_ptr().cast::<V::T>()
+            };
+            let slice = slice::from_raw_parts(base.add(start), end - start);
+
+            Drain {
+                start,
+                tail_start: end,
+                tail_len: len - end,
+                iter: slice.iter(),
+                base,
+                vec,
+                _phantom: PhantomData,
+            }
+        }
+    }
 }
-impl<V: VecLike> Iterator for Drain<'_, V> {
+impl<V: VecLike + ?Sized> Iterator for Drain<'_, V> {
     type Item = V::T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -124,34 +165,36 @@ impl<V: VecLike> Iterator for Drain<'_, V> {
         self.iter.size_hint()
     }
 }
-impl<V: VecLike> DoubleEndedIterator for Drain<'_, V> {
+impl<V: VecLike + ?Sized> DoubleEndedIterator for Drain<'_, V> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let ele = self.iter.next_back()?;
         Some(unsafe { ptr::read(ele) })
     }
 }
-impl<V: VecLike> ExactSizeIterator for Drain<'_, V> {
+impl<V: VecLike + ?Sized> ExactSizeIterator for Drain<'_, V> {
 }
-impl<V: VecLike> FusedIterator for Drain<'_, V> {
+impl<V: VecLike + ?Sized> FusedIterator for Drain<'_, V> {
 }
-unsafe impl<V: VecLike> Send for Drain<'_, V> where V::T: Send {
+unsafe impl<V: VecLike + ?Sized> Send for Drain<'_, V> where V::T: Send {
 }
-unsafe impl<V: VecLike> Sync for Drain<'_, V> where V::T: Sync {
+unsafe impl<V: VecLike + ?Sized> Sync for Drain<'_, V> where V::T: Sync {
 }
-impl<V: VecLike> Drop for Drain<'_, V> {
+impl<V: VecLike + ?Sized> Drop for Drain<'_, V> {
     fn drop(&mut self) {
         let is_zst = mem::size_of::<V::T>() == 0;
 
         let iter = mem::take(&mut self.iter);
         let drop_len = iter.len();
-        let mut vec = self.vec;
+        let base = self.base;
 
         if is_zst {
             unsafe {
-                let vec = self.vec.as_mut();
-                let old_len = vec.len();
-                vec.set_len(old_len + drop_len + self.tail_len);
-                vec.truncate(old_len + self.tail_len);
+                self.vec.as_mut().set_len(self.start + self.tail_len);
+                let to_drop = ptr::slice_from_raw_parts_mut(
+                    NonNull::<V::T>::dangling().as_ptr(),
+                    drop_len,
+                );
+                ptr::drop_in_place(to_drop);
             }
             return;
         }
@@ -165,14 +208,13 @@ impl<V: VecLike> Drop for Drain<'_, V> {
         let drop_ptr = iter.as_slice().as_ptr();
 
         unsafe {
-            let vec_ptr = vec.as_mut().as_mut_ptr();
-            let drop_offset = drop_ptr.offset_from(vec_ptr).try_into().unwrap();
-            let to_drop = ptr::slice_from_raw_parts_mut(vec_ptr.add(drop_offset), drop_len);
+            let drop_offset = drop_ptr.offset_from(base).try_into().unwrap();
+            let to_drop = ptr::slice_from_raw_parts_mut(base.add(drop_offset), drop_len);
             ptr::drop_in_place(to_drop);
         }
     }
 }
-impl<V: VecLike> Drain<'_, V> {
+impl<V: VecLike + ?Sized> Drain<'_, V> {
     /// Get slice of rest elements
     ///
     /// # Examples
@@ -190,7 +232,7 @@ impl<V: VecLike> Drain<'_, V> {
         self.iter.as_slice()
     }
 }
-impl<V: VecLike> Debug for Drain<'_, V>
+impl<V: VecLike + ?Sized> Debug for Drain<'_, V>
 where V::T: Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
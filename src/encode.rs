@@ -0,0 +1,308 @@
+use core::{
+    fmt::{self, Debug},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::{TailVec, VecLike};
+
+macro_rules! impl_put {
+    ($($ty:ty => $le:ident $be:ident;)*) => {
+        impl<V: VecLike<T = u8> + ?Sized> TailVec<'_, u8, V> {
+            $(
+                #[doc = concat!("Write a little-endian [`", stringify!($ty), "`]")]
+                ///
+                /// # Errors
+                /// - [`Err`] when not enough spare capacity,
+                ///   then nothing is written.
+                pub fn $le(&mut self, value: $ty) -> Result<(), $ty> {
+                    self.put_slice(&value.to_le_bytes())
+                        .map_err(|_| value)
+                }
+
+                #[doc = concat!("Write a big-endian [`", stringify!($ty), "`]")]
+                ///
+                /// # Errors
+                /// - [`Err`] when not enough spare capacity,
+                ///   then nothing is written.
+                pub fn $be(&mut self, value: $ty) -> Result<(), $ty> {
+                    self.put_slice(&value.to_be_bytes())
+                        .map_err(|_| value)
+                }
+            )*
+        }
+    };
+}
+impl_put! {
+    u16 => put_u16_le put_u16_be;
+    u32 => put_u32_le put_u32_be;
+    u64 => put_u64_le put_u64_be;
+    u128 => put_u128_le put_u128_be;
+    i16 => put_i16_le put_i16_be;
+    i32 => put_i32_le put_i32_be;
+    i64 => put_i64_le put_i64_be;
+    i128 => put_i128_le put_i128_be;
+    f32 => put_f32_le put_f32_be;
+    f64 => put_f64_le put_f64_be;
+}
+
+impl<'a, V: VecLike<T = u8> + ?Sized> TailVec<'a, u8, V> {
+    /// Copy all bytes of `src` into tail,
+    /// but `src.len()` must be less than or equal spare capacity
+    ///
+    /// With `bytes` feature, the inherent `put_*` methods are called
+    /// instead of the panicking [`BufMut`] methods of the same name,
+    /// call those by path, e.g. `BufMut::put_slice(&mut tail, src)`
+    ///
+    /// # Errors
+    /// - [`Err`] when `src.len()` greater than `capacity() - len()`,
+    ///   then nothing is written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0u8];
+    /// vec.reserve_exact(3);
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// assert_eq!(rest.put_slice(b"ab"), Ok(()));
+    /// assert_eq!(rest.put_slice(b"cd"), Err(&b"cd"[..]));
+    /// assert_eq!(rest, b"ab");
+    /// ```
+    ///
+    /// [`BufMut`]: https://docs.rs/bytes/latest/bytes/trait.BufMut.html
+    pub fn put_slice<'s>(&mut self, src: &'s [u8]) -> Result<(), &'s [u8]> {
+        let len = self.len();
+        let spare = self.spare_capacity_mut();
+        if src.len() > spare.len() {
+            return Err(src);
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(
+                src.as_ptr(),
+                spare.as_mut_ptr().cast(),
+                src.len(),
+            );
+            self.set_len(len + src.len());
+        }
+        Ok(())
+    }
+
+    /// Write a [`u8`]
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailVec::len
+    /// [`capacity()`]: TailVec::capacity
+    pub fn put_u8(&mut self, value: u8) -> Result<(), u8> {
+        self.push(value)
+    }
+
+    /// Write a [`i8`]
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailVec::len
+    /// [`capacity()`]: TailVec::capacity
+    pub fn put_i8(&mut self, value: i8) -> Result<(), i8> {
+        self.push(value as u8).map_err(|_| value)
+    }
+
+    /// Write unsigned LEB128 variable-length integer
+    ///
+    /// # Errors
+    /// - [`Err`] when not enough spare capacity,
+    ///   then nothing is written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(4);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// assert_eq!(rest.put_uvarint(1), Ok(()));
+    /// assert_eq!(rest.put_uvarint(300), Ok(()));
+    /// assert_eq!(rest, [0x01, 0xac, 0x02]);
+    ///
+    /// assert_eq!(rest.put_uvarint(300), Err(300));
+    /// assert_eq!(rest, [0x01, 0xac, 0x02]);
+    /// ```
+    pub fn put_uvarint(&mut self, value: u64) -> Result<(), u64> {
+        let mut buf = [0; 10];
+        let mut n = value;
+        let mut i = 0;
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                buf[i] = byte;
+                i += 1;
+                break;
+            }
+            buf[i] = byte | 0x80;
+            i += 1;
+        }
+        self.put_slice(&buf[..i])
+            .map_err(|_| value)
+    }
+
+    /// Write signed LEB128 variable-length integer, use zigzag encoding
+    ///
+    /// # Errors
+    /// - [`Err`] when not enough spare capacity,
+    ///   then nothing is written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(4);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// assert_eq!(rest.put_ivarint(0), Ok(()));
+    /// assert_eq!(rest.put_ivarint(-1), Ok(()));
+    /// assert_eq!(rest.put_ivarint(1), Ok(()));
+    /// assert_eq!(rest.put_ivarint(-64), Ok(()));
+    /// assert_eq!(rest, [0x00, 0x01, 0x02, 0x7f]);
+    /// ```
+    pub fn put_ivarint(&mut self, value: i64) -> Result<(), i64> {
+        let zigzag = ((value << 1) ^ (value >> 63)) as u64;
+        self.put_uvarint(zigzag)
+            .map_err(|_| value)
+    }
+
+    /// Reserve `N` bytes for length prefix,
+    /// the bytes count written after it is backfilled by `encode` on drop
+    ///
+    /// If the guard [`len()`] is less than the prefix end on drop,
+    /// the prefix is removed.
+    ///
+    /// # Errors
+    /// - [`Err`] when `N` greater than `capacity() - len()`
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(8);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// let encode = |n| (n as u16).to_be_bytes();
+    /// let Ok(mut msg) = rest.reserve_length_prefix(encode) else {
+    ///     unreachable!()
+    /// };
+    /// msg.put_slice(b"abc").unwrap();
+    /// drop(msg);
+    ///
+    /// assert_eq!(rest, [0, 3, b'a', b'b', b'c']);
+    /// ```
+    ///
+    /// [`len()`]: TailVec::len
+    pub fn reserve_length_prefix<F, const N: usize>(
+        &mut self,
+        encode: F,
+    ) -> Result<LengthPrefix<'_, 'a, V, F, N>, F>
+    where F: FnOnce(usize) -> [u8; N],
+    {
+        let start = self.len();
+        if self.put_slice(&[0; N]).is_err() {
+            return Err(encode);
+        }
+        Ok(LengthPrefix {
+            tail: self,
+            start,
+            encode: Some(encode),
+        })
+    }
+}
+
+/// Length prefix backfill guard for [`TailVec`]
+///
+/// This struct is created by [`TailVec::reserve_length_prefix`].
+///
+/// See its documentation for more.
+pub struct LengthPrefix<'r, 'a, V, F, const N: usize>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    tail: &'r mut TailVec<'a, u8, V>,
+    start: usize,
+    encode: Option<F>,
+}
+impl<V, F, const N: usize> LengthPrefix<'_, '_, V, F, N>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    /// Get bytes count written after the prefix
+    #[must_use]
+    pub fn written(&self) -> usize {
+        self.tail.len().saturating_sub(self.start + N)
+    }
+}
+impl<V, F, const N: usize> Drop for LengthPrefix<'_, '_, V, F, N>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    fn drop(&mut self) {
+        let Some(encode) = self.encode.take() else { return };
+        let end = self.start + N;
+
+        if self.tail.len() < end {
+            self.tail.truncate(self.start);
+            return;
+        }
+
+        let prefix = encode(self.tail.len() - end);
+        self.tail[self.start..end].copy_from_slice(&prefix);
+    }
+}
+impl<'a, V, F, const N: usize> Deref for LengthPrefix<'_, 'a, V, F, N>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    type Target = TailVec<'a, u8, V>;
+
+    fn deref(&self) -> &Self::Target {
+        self.tail
+    }
+}
+impl<V, F, const N: usize> DerefMut for LengthPrefix<'_, '_, V, F, N>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.tail
+    }
+}
+impl<V, F, const N: usize> Debug for LengthPrefix<'_, '_, V, F, N>
+where V: VecLike<T = u8> + ?Sized,
+      F: FnOnce(usize) -> [u8; N],
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LengthPrefix")
+            .field("start", &self.start)
+            .field("written", &self.written())
+            .finish()
+    }
+}
+
+/// The inherent `put_*` methods of [`TailVec`] are capacity-checked,
+/// they shadow the panicking methods of the same name,
+/// call these by path, e.g. `BufMut::put_u16_le(&mut tail, n)`
+#[cfg(feature = "bytes")]
+unsafe impl<V: VecLike<T = u8> + ?Sized> bytes::BufMut for TailVec<'_, u8, V> {
+    fn remaining_mut(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        let new_len = self.len() + cnt;
+        assert!(new_len <= self.capacity(),
+                "new_len = {new_len}; capacity = {}", self.capacity());
+        unsafe { self.set_len(new_len) }
+    }
+
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        self.spare_capacity_mut().into()
+    }
+}
@@ -0,0 +1,339 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{TailVec, VecLike};
+
+/// Binary max-heap over [`TailVec`], create from [`into_heap`]
+///
+/// The elements and spare capacity of [`TailVec`] is the heap storage,
+/// it never reallocates, [`push`] fails when capacity is exhausted.
+///
+/// The elements are left in heap order when [`TailHeap`] is dropped,
+/// or in sorted order when [`into_sorted`].
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec![100, 5, 1, 8];
+/// vec.reserve_exact(1);
+/// let (pinned, rest) = vec.split_tail(1);
+/// assert_eq!(pinned, [100]);
+///
+/// let mut heap = rest.into_heap();
+/// assert_eq!(heap.peek(), Some(&8));
+/// heap.push(3).unwrap();
+/// assert_eq!(heap.push(4), Err(4)); // Overflow of capacity
+///
+/// assert_eq!(heap.pop(), Some(8));
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.into_sorted(), [1, 3]);
+///
+/// assert_eq!(vec, [100, 1, 3]);
+/// ```
+///
+/// [`into_heap`]: TailVec::into_heap
+/// [`push`]: TailHeap::push
+/// [`into_sorted`]: TailHeap::into_sorted
+pub struct TailHeap<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    tail: TailVec<'a, T, V>,
+}
+
+impl<'a, T: Ord, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into binary max-heap, the elements are rebuilt in heap order in `O(n)`
+    ///
+    /// *See [`TailHeap`] for more documents*
+    #[must_use]
+    pub fn into_heap(self) -> TailHeap<'a, T, V> {
+        let mut heap = TailHeap { tail: self };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailHeap<'a, T, V> {
+    /// Into [`TailVec`], the elements are in heap order
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        self.tail
+    }
+
+    /// Elements count of the heap
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.len()
+    }
+
+    /// Returns `true` if the heap contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tail.is_empty()
+    }
+
+    /// Capacity of the heap, equal [`TailVec::capacity`]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.tail.capacity()
+    }
+
+    /// Get the elements in heap order
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.tail.as_slice()
+    }
+
+    /// Iterate the elements in heap order
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.tail.iter()
+    }
+
+    /// Remove and drop all elements
+    pub fn clear(&mut self) {
+        self.tail.clear();
+    }
+}
+
+impl<'a, T: Ord, V: VecLike<T = T> + ?Sized> TailHeap<'a, T, V> {
+    fn sift_up(&mut self, mut pos: usize) {
+        let data = self.tail.as_slice_mut();
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if data[pos] <= data[parent] {
+                break;
+            }
+            data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let data = self.tail.as_slice_mut();
+        for pos in (0..data.len() / 2).rev() {
+            sift_down(data, pos);
+        }
+    }
+
+    /// Push a value into heap in `O(log n)`
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailHeap::len
+    /// [`capacity()`]: TailHeap::capacity
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        self.tail.push(value)?;
+        self.sift_up(self.len() - 1);
+        Ok(())
+    }
+
+    /// Pop the greatest value in `O(log n)`
+    ///
+    /// # Results
+    /// - [`None`] when [`len()`] by zero
+    ///
+    /// [`len()`]: TailHeap::len
+    pub fn pop(&mut self) -> Option<T> {
+        let mut value = self.tail.pop()?;
+        if let Some(top) = self.tail.first_mut() {
+            mem::swap(&mut value, top);
+            sift_down(self.tail.as_slice_mut(), 0);
+        }
+        Some(value)
+    }
+
+    /// Get the greatest value
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.tail.first()
+    }
+
+    /// Get the mutable greatest value,
+    /// the heap order is restored when [`PeekMut`] is dropped
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 5, 2];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut heap = rest.into_heap();
+    ///
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 0;
+    /// }
+    /// assert_eq!(heap.peek(), Some(&2));
+    ///
+    /// let top = heap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::pop(top), 2);
+    /// assert_eq!(heap.into_sorted(), [0, 1]);
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, 'a, T, V>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(PeekMut { heap: self })
+    }
+
+    /// Sort the elements in ascending order, and into [`TailVec`]
+    #[must_use]
+    pub fn into_sorted(self) -> TailVec<'a, T, V> {
+        let mut tail = self.tail;
+        let data = tail.as_slice_mut();
+        for end in (1..data.len()).rev() {
+            data.swap(0, end);
+            sift_down(&mut data[..end], 0);
+        }
+        tail
+    }
+
+    /// Pop all elements in descending order
+    ///
+    /// The remaining elements are dropped when [`DrainSorted`] is dropped
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0, 3, 1, 2];
+    /// let (_, rest) = vec.split_tail(1);
+    /// let mut heap = rest.into_heap();
+    ///
+    /// let mut drain = heap.drain_sorted();
+    /// assert_eq!(drain.next(), Some(3));
+    /// assert_eq!(drain.len(), 2);
+    /// drop(drain);
+    ///
+    /// assert!(heap.is_empty());
+    /// drop(heap);
+    /// assert_eq!(vec, [0]);
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, 'a, T, V> {
+        DrainSorted { heap: self }
+    }
+}
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for TailHeap<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tail.fmt(f)
+    }
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Extend<T> for TailHeap<'_, T, V> {
+    /// Push all elements into heap
+    ///
+    /// # Panics
+    /// [`iter.count()`] greater than `capacity() - len()`
+    ///
+    /// [`iter.count()`]: Iterator::count
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|ele| {
+                assert!(self.push(ele).is_ok(),
+                        "Overflow of capacity when extend elements");
+            });
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r TailHeap<'_, T, V> {
+    type Item = &'r T;
+    type IntoIter = slice::Iter<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Sift down `data[pos]`, until it is not less than its children
+fn sift_down<T: Ord>(data: &mut [T], mut pos: usize) {
+    let end = data.len();
+    loop {
+        let mut child = 2 * pos + 1;
+        if child >= end {
+            break;
+        }
+        if child + 1 < end && data[child] < data[child + 1] {
+            child += 1;
+        }
+        if data[pos] >= data[child] {
+            break;
+        }
+        data.swap(pos, child);
+        pos = child;
+    }
+}
+
+/// Mutable greatest element of [`TailHeap`], create from [`TailHeap::peek_mut`]
+pub struct PeekMut<'r, 'a, T: Ord, V: VecLike<T = T> + ?Sized> {
+    heap: &'r mut TailHeap<'a, T, V>,
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> PeekMut<'_, '_, T, V> {
+    /// Remove the peeked value from heap, and return it
+    pub fn pop(this: Self) -> T {
+        let mut this = ManuallyDrop::new(this);
+        // `peek_mut` checked not empty
+        this.heap.pop().unwrap()
+    }
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Drop for PeekMut<'_, '_, T, V> {
+    fn drop(&mut self) {
+        sift_down(self.heap.tail.as_slice_mut(), 0);
+    }
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Deref for PeekMut<'_, '_, T, V> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.tail[0]
+    }
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> DerefMut for PeekMut<'_, '_, T, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.heap.tail[0]
+    }
+}
+impl<T: Ord + Debug, V: VecLike<T = T> + ?Sized> Debug for PeekMut<'_, '_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
+
+/// A draining iterator of [`TailHeap`] in descending order,
+/// create from [`TailHeap::drain_sorted`]
+pub struct DrainSorted<'r, 'a, T: Ord, V: VecLike<T = T> + ?Sized> {
+    heap: &'r mut TailHeap<'a, T, V>,
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Iterator for DrainSorted<'_, '_, T, V> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> ExactSizeIterator for DrainSorted<'_, '_, T, V> {
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> FusedIterator for DrainSorted<'_, '_, T, V> {
+}
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Drop for DrainSorted<'_, '_, T, V> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+impl<T: Ord + Debug, V: VecLike<T = T> + ?Sized> Debug for DrainSorted<'_, '_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DrainSorted").field(&self.heap).finish()
+    }
+}
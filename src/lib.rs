@@ -1,12 +1,64 @@
 #![forbid(unsafe_op_in_unsafe_fn)]
 #![doc = include_str!("../README.md")]
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 mod tailvec;
 mod retain;
 mod drain;
 mod utils;
+mod encode;
+mod push_pop;
+mod storage;
+mod ring;
+mod tail_deque;
+mod heap;
+mod sorted;
+mod slab;
+mod arena;
+mod pinned;
+mod rewrite;
+mod sort;
+#[cfg(feature = "alloc")]
+mod deque;
+mod spsc;
+#[cfg(feature = "alloc")]
+mod scratch;
+#[cfg(any(
+    feature = "arrayvec",
+    feature = "heapless",
+    feature = "smallvec",
+    feature = "bumpalo",
+))]
+mod backend;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
 pub use tailvec::*;
+pub use drain::Drain;
+pub use encode::LengthPrefix;
+pub use push_pop::PushPop;
+pub use storage::{ArrayVecStorage, InlineStorage, InlineVec, RawStorage, SliceStorage};
+pub use ring::{OverflowPolicy, TailRing};
+pub use tail_deque::TailDeque;
+pub use heap::{DrainSorted, PeekMut, TailHeap};
+pub use sorted::SortedTailVec;
+pub use slab::{SlabIter, SlabIterMut, TailSlab};
+pub use arena::ArenaTail;
+pub use pinned::{PinIterMut, PinnedTail};
+pub use rewrite::Emitter;
+#[cfg(feature = "alloc")]
+pub use deque::DequeVec;
+pub use spsc::{Consumer, Producer, SplitSpsc};
+#[cfg(feature = "alloc")]
+pub use scratch::{ScratchPool, WithScratch};
+#[cfg(feature = "bumpalo")]
+pub use backend::BumpTailVec;
+#[cfg(feature = "serde")]
+pub use serde_impl::DeserializeInto;
 
 #[cfg(test)]
 #[cfg(feature = "std")]
@@ -0,0 +1,242 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    pin::Pin,
+    ptr,
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{TailVec, VecLike};
+
+/// Pinned view of [`TailVec`], create from [`with_pinned`]
+///
+/// Only non-moving operations are available,
+/// the pushed elements are pinned, and never move until dropped in place.
+///
+/// The pushed elements are dropped in place when [`with_pinned`] returns,
+/// or unwinds, so [`Pin`] drop guarantee holds.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// use std::{marker::PhantomPinned, pin::Pin, ptr};
+///
+/// struct Node {
+///     value: i32,
+///     prev: *const Node,
+///     _pin: PhantomPinned,
+/// }
+///
+/// let mut vec = Vec::with_capacity(3);
+/// let (_, mut rest) = vec.split_tail(0);
+///
+/// let sum = rest.with_pinned(|pinned| {
+///     let mut prev = ptr::null();
+///     for value in 1..=3 {
+///         let node = pinned.push_pinned(Node { value, prev, _pin: PhantomPinned });
+///         let Ok(node) = node else { unreachable!() };
+///         prev = &*node;
+///     }
+///     let mut sum = 0;
+///     while let Some(node) = unsafe { prev.as_ref() } {
+///         sum += node.value;
+///         prev = node.prev;
+///     }
+///     sum
+/// });
+/// assert_eq!(sum, 6);
+/// assert!(rest.is_empty()); // Dropped in place
+/// ```
+///
+/// [`with_pinned`]: TailVec::with_pinned
+pub struct PinnedTail<
+    'r,
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    tail: &'r mut TailVec<'a, T, V>,
+    start: usize,
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Call `f` with a pinned view of the spare capacity,
+    /// the existing elements are not pinned and are not accessible in `f`
+    ///
+    /// *See [`PinnedTail`] for more documents*
+    pub fn with_pinned<R, F>(&mut self, f: F) -> R
+    where F: FnOnce(&mut PinnedTail<'_, 'a, T, V>) -> R,
+    {
+        let start = self.len();
+        let mut pinned = PinnedTail { tail: self, start };
+        f(&mut pinned)
+    }
+}
+
+impl<T, V: VecLike<T = T> + ?Sized> PinnedTail<'_, '_, T, V> {
+    /// Pinned elements count
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.len() - self.start
+    }
+
+    /// Returns `true` if it contains no pinned elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Capacity for the pinned elements
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.tail.capacity() - self.start
+    }
+
+    /// Get pinned elements
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.tail.as_slice()[self.start..]
+    }
+
+    fn as_slice_mut(&mut self) -> &mut [T] {
+        let start = self.start;
+        &mut self.tail.as_slice_mut()[start..]
+    }
+
+    /// Push a value, and return it pinned
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: PinnedTail::len
+    /// [`capacity()`]: PinnedTail::capacity
+    pub fn push_pinned(&mut self, value: T) -> Result<Pin<&mut T>, T> {
+        self.tail.push(value)?;
+        let last = self.tail.last_mut().unwrap();
+        Ok(unsafe { Pin::new_unchecked(last) })
+    }
+
+    /// Get the pinned element of index
+    pub fn get_pin_mut(&mut self, index: usize) -> Option<Pin<&mut T>> {
+        let ele = self.as_slice_mut().get_mut(index)?;
+        Some(unsafe { Pin::new_unchecked(ele) })
+    }
+
+    /// Iterate the pinned elements
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Iterate the pinned elements mutably
+    pub fn iter_pin_mut(&mut self) -> PinIterMut<'_, T> {
+        PinIterMut { iter: self.as_slice_mut().iter_mut() }
+    }
+
+    /// Shortens, keeping the first `len` pinned elements,
+    /// and dropping the rest in place
+    ///
+    /// The rest are dropped from front to back, like [`ptr::drop_in_place`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0];
+    /// vec.reserve_exact(3);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// rest.with_pinned(|pinned| {
+    ///     pinned.extend([1, 2, 3]);
+    ///     pinned.truncate(1);
+    ///     assert_eq!(pinned.as_slice(), [1]);
+    /// });
+    /// assert_eq!(rest, [0]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        let rest: *mut [T] = &mut self.as_slice_mut()[len..];
+        unsafe {
+            self.tail.set_len(self.start + len);
+            ptr::drop_in_place(rest);
+        }
+    }
+
+    /// Drop all pinned elements in place
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Drop for PinnedTail<'_, '_, T, V> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for PinnedTail<'_, '_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Extend<T> for PinnedTail<'_, '_, T, V> {
+    /// Push all elements
+    ///
+    /// # Panics
+    /// [`iter.count()`] greater than `capacity() - len()`
+    ///
+    /// [`iter.count()`]: Iterator::count
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|ele| {
+                assert!(self.push_pinned(ele).is_ok(),
+                        "Overflow of capacity when extend elements");
+            });
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r PinnedTail<'_, '_, T, V> {
+    type Item = &'r T;
+    type IntoIter = slice::Iter<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r mut PinnedTail<'_, '_, T, V> {
+    type Item = Pin<&'r mut T>;
+    type IntoIter = PinIterMut<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_pin_mut()
+    }
+}
+
+/// Iterator of [`PinnedTail::iter_pin_mut`]
+#[derive(Debug)]
+pub struct PinIterMut<'r, T> {
+    iter: slice::IterMut<'r, T>,
+}
+impl<'r, T> Iterator for PinIterMut<'r, T> {
+    type Item = Pin<&'r mut T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|ele| unsafe { Pin::new_unchecked(ele) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+impl<T> DoubleEndedIterator for PinIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|ele| unsafe { Pin::new_unchecked(ele) })
+    }
+}
+impl<T> ExactSizeIterator for PinIterMut<'_, T> {
+}
+impl<T> FusedIterator for PinIterMut<'_, T> {
+}
@@ -0,0 +1,116 @@
+use core::{
+    mem::{size_of, MaybeUninit},
+    ptr,
+};
+
+use bytemuck::Pod;
+
+use crate::{TailVec, VecLike};
+
+impl<T: Pod, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
+    /// Get initialized elements as bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0u16, 0x0102];
+    /// let (_, rest) = vec.split_tail(1);
+    /// assert_eq!(rest.as_bytes(), 0x0102u16.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.as_slice())
+    }
+
+    /// Get initialized elements as mutable bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0u16, 0];
+    /// let (_, mut rest) = vec.split_tail(1);
+    /// rest.as_bytes_mut().fill(0xff);
+    /// assert_eq!(rest, [0xffff]);
+    /// ```
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(self.as_slice_mut())
+    }
+
+    /// Get uninitialized partials as bytes
+    ///
+    /// After writing, use [`set_len`] to commit written elements,
+    /// written bytes must be a multiple of `size_of::<T>()`
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::<u16>::with_capacity(3);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// let spare = rest.spare_bytes_mut();
+    /// assert_eq!(spare.len(), 6);
+    /// for byte in &mut spare[..4] {
+    ///     byte.write(1);
+    /// }
+    /// unsafe { rest.set_len(2) }
+    /// assert_eq!(rest, [0x0101, 0x0101]);
+    /// ```
+    ///
+    /// [`set_len`]: VecLike::set_len
+    pub fn spare_bytes_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        let spare = self.spare_capacity_mut();
+        let len = spare.len() * size_of::<T>();
+        unsafe {
+            &mut *ptr::slice_from_raw_parts_mut(spare.as_mut_ptr().cast(), len)
+        }
+    }
+
+    /// Copy elements from bytes into tail,
+    /// the bytes length must be a multiple of `size_of::<T>()`
+    ///
+    /// `bytes` does not need to be aligned.
+    ///
+    /// # Errors
+    /// - [`Err`] when `bytes.len()` is not a multiple of `size_of::<T>()`
+    /// - [`Err`] when elements count greater than `capacity() - len()`
+    ///
+    /// When an error occurs, nothing is written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![[0u8; 2]];
+    /// vec.reserve_exact(2);
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// assert_eq!(rest.extend_from_bytes(b"abc"), Err(&b"abc"[..]));
+    /// assert_eq!(rest.extend_from_bytes(b"abcd"), Ok(()));
+    /// assert_eq!(rest.extend_from_bytes(b"ef"), Err(&b"ef"[..]));
+    /// assert_eq!(rest, [*b"ab", *b"cd"]);
+    /// ```
+    pub fn extend_from_bytes<'s>(&mut self, bytes: &'s [u8]) -> Result<(), &'s [u8]> {
+        let size = size_of::<T>();
+        if size == 0 {
+            return if bytes.is_empty() { Ok(()) } else { Err(bytes) };
+        }
+        if !bytes.len().is_multiple_of(size) {
+            return Err(bytes);
+        }
+
+        let count = bytes.len() / size;
+        let len = self.len();
+        let spare = self.spare_bytes_mut();
+        if bytes.len() > spare.len() {
+            return Err(bytes);
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                spare.as_mut_ptr().cast(),
+                bytes.len(),
+            );
+            self.set_len(len + count);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use core::ops::RangeBounds;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::{self, Vec};
+#[cfg(feature = "std")]
+use std::vec;
+
+use crate::{
+    ArrayVecStorage,
+    Drain,
+    InlineStorage,
+    InlineVec,
+    RawStorage,
+    SliceStorage,
+    TailVec,
+    VecLike,
+};
+
+/// High-level stack operations over [`Vec`], [`TailVec`],
+/// fixed-capacity storages and the vectors of backend features
+///
+/// [`Vec`] and the growable backends are never full,
+/// the others fail when capacity is exhausted.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// fn fill<S: PushPop<T = i32>>(stack: &mut S) -> usize {
+///     let mut n = 0;
+///     while n < 4 && stack.try_push(n as i32).is_ok() {
+///         n += 1;
+///     }
+///     n
+/// }
+///
+/// let mut vec = Vec::new();
+/// assert_eq!(fill(&mut vec), 4);
+///
+/// let mut vec = Vec::with_capacity(3);
+/// vec.push(-1);
+/// let (_, mut rest) = vec.split_tail(1);
+/// assert_eq!(fill(&mut rest), 2);
+///
+/// let mut storage = ArrayVecStorage::<i32, 3>::new();
+/// assert_eq!(fill(&mut storage), 3);
+/// ```
+pub trait PushPop: VecLike {
+    /// Draining iterator of [`drain`]
+    ///
+    /// [`drain`]: PushPop::drain
+    type Drain<'a>: Iterator<Item = Self::T>
+    where Self: 'a;
+
+    /// Push a value
+    ///
+    /// # Errors
+    /// - [`Err`] when [`remaining_capacity()`] by zero
+    ///
+    /// [`remaining_capacity()`]: PushPop::remaining_capacity
+    fn try_push(&mut self, value: Self::T) -> Result<(), Self::T> {
+        let len = self.len();
+        let Some(slot) = self.spare_capacity_mut().first_mut() else {
+            return Err(value);
+        };
+        slot.write(value);
+        unsafe { self.set_len(len + 1) }
+        Ok(())
+    }
+
+    /// Pop last value
+    fn pop(&mut self) -> Option<Self::T> {
+        let new_len = self.len().checked_sub(1)?;
+        unsafe {
+            self.set_len(new_len);
+            Some(self.spare_capacity_mut()[0].assume_init_read())
+        }
+    }
+
+    /// Shortens, keeping the first `len` elements, and dropping the rest.
+    fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.pop();
+        }
+    }
+
+    /// Count of elements that can still be pushed
+    fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Push all elements of `iter`
+    ///
+    /// # Errors
+    /// - [`Err`] with the first element that cannot be pushed,
+    ///   then the elements before it are pushed
+    ///   and the elements after it are not consumed.
+    fn try_extend<I>(&mut self, iter: I) -> Result<(), Self::T>
+    where I: IntoIterator<Item = Self::T>,
+          Self: Sized,
+    {
+        iter.into_iter()
+            .try_for_each(|ele| self.try_push(ele))
+    }
+
+    /// Removes the specified range in bulk, returning all removed elements
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length.
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> PushPop for Vec<T> {
+    type Drain<'a> = vec::Drain<'a, T>
+    where Self: 'a;
+
+    fn try_push(&mut self, value: T) -> Result<(), T> {
+        self.push(value);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    /// [`Vec`] is never full
+    fn remaining_capacity(&self) -> usize {
+        usize::MAX - self.len()
+    }
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        self.drain(range)
+    }
+}
+
+impl<T, V: VecLike<T = T> + ?Sized> PushPop for TailVec<'_, T, V> {
+    type Drain<'a> = Drain<'a, V>
+    where Self: 'a;
+
+    fn try_push(&mut self, value: T) -> Result<(), T> {
+        self.push(value)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        self.drain(range)
+    }
+}
+
+impl<T, const N: usize> PushPop for ArrayVecStorage<T, N> {
+    type Drain<'a> = Drain<'a, Self>
+    where Self: 'a;
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        Drain::new(self, range)
+    }
+}
+
+impl<T> PushPop for SliceStorage<'_, T> {
+    type Drain<'a> = Drain<'a, Self>
+    where Self: 'a;
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        Drain::new(self, range)
+    }
+}
+
+impl<T> PushPop for RawStorage<'_, T> {
+    type Drain<'a> = Drain<'a, Self>
+    where Self: 'a;
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        Drain::new(self, range)
+    }
+}
+
+impl<V: InlineVec> PushPop for InlineStorage<V> {
+    type Drain<'a> = Drain<'a, Self>
+    where Self: 'a;
+
+    fn drain<R>(&mut self, range: R) -> Self::Drain<'_>
+    where R: RangeBounds<usize>,
+    {
+        Drain::new(self, range)
+    }
+}
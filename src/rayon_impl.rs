@@ -0,0 +1,402 @@
+//! The extend consumer comes from rayon's `collect` consumer
+
+use core::{
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    slice,
+};
+
+use rayon::{
+    iter::plumbing::{Consumer, Folder, Reducer, UnindexedConsumer},
+    prelude::*,
+};
+
+use crate::{TailVec, VecLike};
+
+impl<T: Send, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
+    /// Initialize `n` spare slots in parallel, the slot `i` is `f(i)`
+    ///
+    /// The elements are committed only if all of `f` calls succeed,
+    /// if `f` panics, the initialized elements are dropped
+    /// and [`len()`] will not change.
+    ///
+    /// # Errors
+    /// - `n` greater than `capacity() - len()`
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0xff];
+    /// vec.reserve_exact(1000);
+    /// let (header, mut rest) = vec.split_tail(1);
+    /// assert_eq!(header, [0xff]);
+    ///
+    /// assert!(rest.par_extend_from_fn(1000, |i| i * 2).is_ok());
+    /// assert!(rest.par_extend_from_fn(1, |i| i).is_err()); // Overflow of capacity
+    /// drop(rest);
+    ///
+    /// assert_eq!(vec.len(), 1001);
+    /// assert_eq!(vec[1..4], [0, 2, 4]);
+    /// assert_eq!(vec[1000], 1998);
+    /// ```
+    ///
+    /// [`len()`]: TailVec::len
+    pub fn par_extend_from_fn<F>(&mut self, n: usize, f: F) -> Result<(), F>
+    where F: Fn(usize) -> T + Sync + Send,
+    {
+        if n > self.capacity() - self.len() {
+            return Err(f);
+        }
+        extend_with_consumer(self, n, |consumer| {
+            (0..n).into_par_iter().map(f).drive(consumer)
+        });
+        Ok(())
+    }
+
+    /// Parallel version of [`retain`]
+    ///
+    /// The elements are split into chunks for each thread,
+    /// it preserves the order of the retained elements,
+    /// but the predicate is called in arbitrary order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = (0..1000).collect::<Vec<_>>();
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// rest.par_retain(|n| n % 3 == 0);
+    /// assert_eq!(rest.len(), 333);
+    /// assert_eq!(rest[..3], [3, 6, 9]);
+    /// drop(rest);
+    ///
+    /// assert_eq!(vec[..3], [0, 3, 6]);
+    /// ```
+    ///
+    /// [`retain`]: TailVec::retain
+    pub fn par_retain<F>(&mut self, f: F)
+    where F: Fn(&T) -> bool + Sync,
+    {
+        self.par_retain_mut(|ele| f(ele));
+    }
+
+    /// Parallel version of [`retain_mut`]
+    ///
+    /// See [`par_retain`] for more documents
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = (0..100).collect::<Vec<_>>();
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// rest.par_retain_mut(|n| {
+    ///     *n *= 2;
+    ///     *n < 10
+    /// });
+    /// assert_eq!(rest, [0, 2, 4, 6, 8]);
+    /// ```
+    ///
+    /// [`retain_mut`]: TailVec::retain_mut
+    /// [`par_retain`]: TailVec::par_retain
+    pub fn par_retain_mut<F>(&mut self, f: F)
+    where F: Fn(&mut T) -> bool + Sync,
+    {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let chunk_size = len.div_ceil(rayon::current_num_threads());
+
+        let base = unsafe {
+            self.set_len(0);
+            self.parts().as_mut_ptr()
+        };
+        let mut g = CompactGuard {
+            this: self,
+            base,
+            chunk_size,
+            // Untouched chunks are still fully initialized
+            counts: (0..len).step_by(chunk_size)
+                .map(|start| chunk_size.min(len - start))
+                .collect(),
+        };
+
+        let parts = unsafe { slice::from_raw_parts_mut(base, len) };
+        parts.par_chunks_mut(chunk_size)
+            .zip(g.counts.par_iter_mut())
+            .for_each(|(chunk, count)| retain_chunk(chunk, count, &f));
+
+        drop(g);
+    }
+}
+
+/// Moves the retained prefix of each chunk together
+struct CompactGuard<'r, 'a, V: VecLike + ?Sized> {
+    this: &'r mut TailVec<'a, V::T, V>,
+    base: *mut MaybeUninit<V::T>,
+    chunk_size: usize,
+    counts: Vec<usize>,
+}
+impl<V: VecLike + ?Sized> Drop for CompactGuard<'_, '_, V> {
+    fn drop(&mut self) {
+        let mut new_len = 0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            let start = i * self.chunk_size;
+            if start != new_len {
+                unsafe {
+                    let src = self.base.add(start);
+                    let dst = self.base.add(new_len);
+                    ptr::copy(src, dst, count);
+                }
+            }
+            new_len += count;
+        }
+        unsafe { self.this.set_len(new_len) }
+    }
+}
+
+/// Sequential retain of a chunk, the retained elements count writes to `count`
+fn retain_chunk<T, F>(chunk: &mut [MaybeUninit<T>], count: &mut usize, f: &F)
+where F: Fn(&mut T) -> bool,
+{
+    struct Guard<'c, T> {
+        chunk: &'c mut [MaybeUninit<T>],
+        count: &'c mut usize,
+        proced_len: usize,
+        deleted_cnt: usize,
+    }
+    impl<T> Drop for Guard<'_, T> {
+        fn drop(&mut self) {
+            let orig_len = self.chunk.len();
+            if self.deleted_cnt > 0 {
+                unsafe {
+                    let src = self.chunk.as_mut_ptr().add(self.proced_len);
+                    let dst = src.sub(self.deleted_cnt);
+                    ptr::copy(src, dst, orig_len - self.proced_len);
+                }
+            }
+            *self.count = orig_len - self.deleted_cnt;
+        }
+    }
+
+    let mut g = Guard { chunk, count, proced_len: 0, deleted_cnt: 0 };
+
+    while g.proced_len != g.chunk.len() {
+        let cur = unsafe {
+            g.chunk.as_mut_ptr().add(g.proced_len).cast::<T>()
+        };
+        if !f(unsafe { &mut *cur }) {
+            g.proced_len += 1;
+            g.deleted_cnt += 1;
+            unsafe { ptr::drop_in_place(cur) }
+            continue;
+        }
+        if g.deleted_cnt > 0 {
+            unsafe {
+                let hole_slot = cur.sub(g.deleted_cnt);
+                ptr::copy_nonoverlapping(cur, hole_slot, 1);
+            }
+        }
+        g.proced_len += 1;
+    }
+}
+
+impl<T: Send, V: VecLike<T = T> + ?Sized> ParallelExtend<T> for &mut TailVec<'_, T, V> {
+    /// Extends a collection with the contents of a parallel iterator.
+    ///
+    /// The elements of [`IndexedParallelIterator`] are written into
+    /// the spare capacity in parallel,
+    /// others are collected into temporary vectors first.
+    ///
+    /// # Panics
+    /// Elements count greater than `capacity() - len()`
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut vec = Vec::with_capacity(8);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// (&mut rest).par_extend((0..4).into_par_iter().map(|n| n * n));
+    /// (&mut rest).par_extend((0..10).into_par_iter().filter(|n| n % 4 == 1));
+    /// assert_eq!(rest, [0, 1, 4, 9, 1, 5, 9]);
+    /// ```
+    #[track_caller]
+    fn par_extend<I>(&mut self, par_iter: I)
+    where I: IntoParallelIterator<Item = T>,
+    {
+        let par_iter = par_iter.into_par_iter();
+
+        if let Some(len) = par_iter.opt_len() {
+            extend_with_consumer(self, len, |consumer| {
+                par_iter.drive_unindexed(consumer)
+            });
+        } else {
+            let list = par_iter.collect_vec_list();
+            let len = list.iter().map(Vec::len).sum::<usize>();
+            assert!(len <= self.capacity() - self.len(),
+                    "Overflow of capacity when extend elements");
+            list.into_iter().for_each(|vec| self.extend(vec));
+        }
+    }
+}
+
+/// Write exactly `len` elements into the spare capacity, then commit them
+#[track_caller]
+fn extend_with_consumer<T, V, F>(this: &mut TailVec<'_, T, V>, len: usize, scope_fn: F)
+where T: Send,
+      V: VecLike<T = T> + ?Sized,
+      F: FnOnce(ExtendConsumer<'_, T>) -> ExtendResult<'_, T>,
+{
+    let old_len = this.len();
+    assert!(len <= this.capacity() - old_len,
+            "Overflow of capacity when extend elements");
+
+    let start = this.spare_capacity_mut().as_mut_ptr().cast::<T>();
+    let result = scope_fn(unsafe { ExtendConsumer::new(start, len) });
+
+    let actual_writes = result.initialized_len;
+    assert!(actual_writes == len,
+            "expected {len} total writes, but got {actual_writes}");
+
+    let len = result.release_ownership();
+    unsafe { this.set_len(old_len + len) }
+}
+
+struct SendPtr<T>(*mut T);
+impl<T> Clone for SendPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SendPtr<T> {
+}
+unsafe impl<T: Send> Send for SendPtr<T> {
+}
+unsafe impl<T: Send> Sync for SendPtr<T> {
+}
+
+struct ExtendConsumer<'c, T> {
+    start: SendPtr<T>,
+    len: usize,
+    _phantom: PhantomData<&'c mut T>,
+}
+impl<T> ExtendConsumer<'_, T> {
+    /// # Safety
+    /// `start` must be valid for writes of `len` elements,
+    /// the target memory is considered uninitialized
+    unsafe fn new(start: *mut T, len: usize) -> Self {
+        Self { start: SendPtr(start), len, _phantom: PhantomData }
+    }
+}
+
+/// The initialized part of the target slice,
+/// elements are dropped when it is dropped, unless ownership is released
+struct ExtendResult<'c, T> {
+    start: SendPtr<T>,
+    total_len: usize,
+    initialized_len: usize,
+    _phantom: PhantomData<&'c mut &'c mut [T]>,
+}
+impl<T> ExtendResult<'_, T> {
+    fn release_ownership(mut self) -> usize {
+        let len = self.initialized_len;
+        self.initialized_len = 0;
+        len
+    }
+}
+impl<T> Drop for ExtendResult<'_, T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.start.0,
+                self.initialized_len,
+            ));
+        }
+    }
+}
+
+impl<'c, T: Send + 'c> Consumer<T> for ExtendConsumer<'c, T> {
+    type Folder = ExtendResult<'c, T>;
+    type Reducer = ExtendReducer;
+    type Result = ExtendResult<'c, T>;
+
+    fn split_at(self, index: usize) -> (Self, Self, Self::Reducer) {
+        assert!(index <= self.len);
+        unsafe {
+            (
+                ExtendConsumer::new(self.start.0, index),
+                ExtendConsumer::new(self.start.0.add(index), self.len - index),
+                ExtendReducer,
+            )
+        }
+    }
+
+    fn into_folder(self) -> Self::Folder {
+        ExtendResult {
+            start: self.start,
+            total_len: self.len,
+            initialized_len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn full(&self) -> bool {
+        false
+    }
+}
+/// Only used when [`ParallelIterator::opt_len`] is known,
+/// then it is driven as indexed
+impl<'c, T: Send + 'c> UnindexedConsumer<T> for ExtendConsumer<'c, T> {
+    fn split_off_left(&self) -> Self {
+        unreachable!("ExtendConsumer must be indexed!")
+    }
+
+    fn to_reducer(&self) -> Self::Reducer {
+        ExtendReducer
+    }
+}
+
+impl<'c, T: Send + 'c> Folder<T> for ExtendResult<'c, T> {
+    type Result = Self;
+
+    fn consume(mut self, item: T) -> Self {
+        assert!(self.initialized_len < self.total_len,
+                "too many values pushed to consumer");
+        unsafe {
+            self.start.0.add(self.initialized_len).write(item);
+        }
+        self.initialized_len += 1;
+        self
+    }
+
+    fn complete(self) -> Self::Result {
+        self
+    }
+
+    fn full(&self) -> bool {
+        false
+    }
+}
+
+/// Combines adjacent results, non-adjacent right part is dropped,
+/// then the total length check fails
+struct ExtendReducer;
+impl<'c, T> Reducer<ExtendResult<'c, T>> for ExtendReducer {
+    fn reduce(
+        self,
+        mut left: ExtendResult<'c, T>,
+        right: ExtendResult<'c, T>,
+    ) -> ExtendResult<'c, T> {
+        let left_end = unsafe { left.start.0.add(left.initialized_len) };
+        if left_end == right.start.0 {
+            left.total_len += right.total_len;
+            left.initialized_len += right.release_ownership();
+        }
+        left
+    }
+}
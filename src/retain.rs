@@ -3,7 +3,7 @@
 use core::ptr;
 use super::{TailVec, VecLike};
 
-impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements `e` such that `f(&e)` returns `false`.
@@ -72,14 +72,14 @@ impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
     pub fn retain_mut<F>(&mut self, mut f: F)
     where F: FnMut(&mut T) -> bool,
     {
-        struct Guard<'a, 'b, V: VecLike> {
+        struct Guard<'a, 'b, V: VecLike + ?Sized> {
             this: &'b mut TailVec<'a, V::T, V>,
             orig_len: usize,
             proced_len: usize,
             deleted_cnt: usize,
         }
 
-        impl<V: VecLike> Drop for Guard<'_, '_, V> {
+        impl<V: VecLike + ?Sized> Drop for Guard<'_, '_, V> {
             fn drop(&mut self) {
                 let Self {
                     ref mut this,
@@ -104,7 +104,7 @@ impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
             }
         }
 
-        impl<V: VecLike> Guard<'_, '_, V> {
+        impl<V: VecLike + ?Sized> Guard<'_, '_, V> {
             fn run<F, const DELETED: bool>(&mut self, f: &mut F)
             where F: FnMut(&mut V::T) -> bool,
             {
@@ -120,7 +120,7 @@ impl<T, V: VecLike<T = T>> TailVec<'_, T, V> {
                         self.proced_len += 1;
                         self.deleted_cnt += 1;
 
-                        unsafe { ptr::drop_in_place(cur) }
+                        unsafe { cur.assume_init_drop() }
 
                         if DELETED {
                             continue;
@@ -0,0 +1,163 @@
+use core::{
+    fmt::{self, Debug},
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr::{self, NonNull},
+};
+
+use crate::{TailVec, VecLike};
+
+/// Output writer of [`TailVec::rewrite`]
+pub struct Emitter<'r, T> {
+    buf: NonNull<MaybeUninit<T>>,
+    written: &'r mut usize,
+    limit: usize,
+}
+impl<T> Emitter<'_, T> {
+    /// Write an output value
+    ///
+    /// # Errors
+    /// - [`Err`] when [`remaining()`] by zero
+    ///
+    /// [`remaining()`]: Emitter::remaining
+    pub fn emit(&mut self, value: T) -> Result<(), T> {
+        if *self.written == self.limit {
+            return Err(value);
+        }
+        unsafe { (*self.buf.add(*self.written).as_ptr()).write(value); }
+        *self.written += 1;
+        Ok(())
+    }
+
+    /// Count of outputs can still be written
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.limit - *self.written
+    }
+}
+impl<T> Debug for Emitter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Emitter")
+            .field("written", &self.written)
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
+
+impl<T, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
+    /// Replace each element with zero or more outputs of `f`, without allocating
+    ///
+    /// The elements are moved to the end of capacity first,
+    /// outputs are written from the front of capacity,
+    /// so total count of outputs can be up to `capacity() - len()`.
+    ///
+    /// The elements are dropped after all outputs are written.
+    ///
+    /// # Errors
+    /// - [`Err`] when `f` returns [`Err`],
+    ///   usually the output rejected by [`Emitter::emit`].
+    ///   Then the outputs are dropped, and the original elements are restored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec!["pre", "a*2", "b", "c*0"];
+    /// vec.reserve_exact(4);
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// let res = rest.rewrite(|&item, out| {
+    ///     let (name, count) = item.split_once('*').unwrap_or((item, "1"));
+    ///     for _ in 0..count.parse().unwrap() {
+    ///         out.emit(name)?;
+    ///     }
+    ///     Ok(())
+    /// });
+    /// assert_eq!(res, Ok(()));
+    /// assert_eq!(rest, ["a", "a", "b"]);
+    ///
+    /// let res = rest.rewrite(|item, out| {
+    ///     out.emit(item)?;
+    ///     out.emit(item)
+    /// });
+    /// assert_eq!(res, Err("b")); // Overflow of capacity
+    /// assert_eq!(rest, ["a", "a", "b"]);
+    /// ```
+    pub fn rewrite<F>(&mut self, mut f: F) -> Result<(), T>
+    where F: FnMut(&T, &mut Emitter<'_, T>) -> Result<(), T>,
+    {
+        struct Guard<'a, 'b, V: VecLike + ?Sized> {
+            this: &'b mut TailVec<'a, V::T, V>,
+            orig_len: usize,
+            offset: usize,
+            written: usize,
+        }
+        impl<V: VecLike + ?Sized> Drop for Guard<'_, '_, V> {
+            /// Drop outputs and restore the original elements
+            fn drop(&mut self) {
+                unsafe {
+                    let ptr = self.this.parts().as_mut_ptr().cast::<V::T>();
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, self.written));
+                    ptr::copy(ptr.add(self.offset), ptr, self.orig_len);
+                    self.this.set_len(self.orig_len);
+                }
+            }
+        }
+
+        let orig_len = self.len();
+        let offset = self.capacity() - orig_len;
+        unsafe {
+            let ptr = self.as_mut_ptr();
+            ptr::copy(ptr, ptr.add(offset), orig_len);
+            self.set_len(0);
+        }
+        let mut guard = Guard { this: self, orig_len, offset, written: 0 };
+
+        let buf = unsafe { NonNull::from(guard.this.parts()).cast::<MaybeUninit<T>>() };
+        for i in 0..orig_len {
+            let item = unsafe { &*buf.add(offset + i).as_ptr().cast::<T>() };
+            let mut emitter = Emitter { buf, written: &mut guard.written, limit: offset };
+            f(item, &mut emitter)?;
+        }
+
+        let guard = ManuallyDrop::new(guard);
+        let written = guard.written;
+        let this = unsafe { ptr::read(&guard.this) };
+        unsafe {
+            this.set_len(written);
+            let ptr = buf.add(offset).as_ptr().cast::<T>();
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, orig_len));
+        }
+        Ok(())
+    }
+
+    /// Replace each element with the outputs of `f`, without allocating
+    ///
+    /// *See [`rewrite`] for more documents*
+    ///
+    /// # Errors
+    /// - [`Err`] with the first output that cannot be written,
+    ///   then the outputs are dropped, and the original elements are restored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 0, 3];
+    /// vec.reserve_exact(4);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// assert_eq!(rest.flat_map_in_place(|&n| vec![n; n]), Ok(()));
+    /// assert_eq!(rest, [1, 3, 3, 3]);
+    ///
+    /// assert_eq!(rest.flat_map_in_place(|&n| [n, n + 1]), Err(4));
+    /// assert_eq!(rest, [1, 3, 3, 3]);
+    /// ```
+    ///
+    /// [`rewrite`]: TailVec::rewrite
+    pub fn flat_map_in_place<I, F>(&mut self, mut f: F) -> Result<(), T>
+    where F: FnMut(&T) -> I,
+          I: IntoIterator<Item = T>,
+    {
+        self.rewrite(|item, out| {
+            f(item).into_iter().try_for_each(|value| out.emit(value))
+        })
+    }
+}
@@ -0,0 +1,419 @@
+use core::{
+    fmt::{self, Debug},
+    iter::Chain,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::Range,
+    ptr::{self, NonNull},
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{
+    tailvec::{slice_assume_init, slice_assume_init_mut},
+    TailVec,
+    VecLike,
+};
+
+/// What [`TailRing::push`] does when the ring is full
+pub enum OverflowPolicy<'a, T> {
+    /// Reject the new element, [`TailRing::push`] returns [`Err`]
+    Reject,
+    /// Drop the oldest element
+    Overwrite,
+    /// Pass the oldest element to callback
+    Callback(&'a mut (dyn FnMut(T) + Send)),
+}
+impl<T> Debug for OverflowPolicy<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reject => f.write_str("Reject"),
+            Self::Overwrite => f.write_str("Overwrite"),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+/// Fixed-capacity ring buffer over [`TailVec`], create from [`into_ring`]
+///
+/// The elements wrap around in the [`TailVec`] capacity,
+/// when the ring is full, [`push`] follows the [`OverflowPolicy`].
+///
+/// The elements are rotated into logical order when [`TailRing`] is dropped,
+/// or [`into_tail`], then the vector stays contiguous.
+///
+/// # Leaking
+/// If the [`TailRing`] goes out of scope without being dropped
+/// (due to [`mem::forget`], for example),
+/// the elements of the ring are leaked.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec!["header"];
+/// vec.reserve_exact(3);
+/// let (_, rest) = vec.split_tail(1);
+///
+/// let mut ring = rest.into_ring(OverflowPolicy::Overwrite);
+/// for event in ["a", "b", "c", "d", "e"] {
+///     ring.push(event).unwrap();
+/// }
+/// assert_eq!(ring.as_slices(), (&["c"][..], &["d", "e"][..]));
+/// assert!(ring.iter().eq(&["c", "d", "e"]));
+/// drop(ring);
+///
+/// assert_eq!(vec, ["header", "c", "d", "e"]);
+/// ```
+///
+/// [`into_ring`]: TailVec::into_ring
+/// [`push`]: TailRing::push
+/// [`into_tail`]: TailRing::into_tail
+/// [`mem::forget`]: core::mem::forget
+pub struct TailRing<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    tail: TailVec<'a, T, V>,
+    buf: NonNull<[MaybeUninit<T>]>,
+    head: usize,
+    len: usize,
+    policy: OverflowPolicy<'a, T>,
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into fixed-capacity ring buffer, the elements are kept
+    ///
+    /// *See [`TailRing`] for more documents*
+    #[must_use]
+    pub fn into_ring(mut self, policy: OverflowPolicy<'a, T>) -> TailRing<'a, T, V> {
+        let len = self.len();
+        let buf = unsafe {
+            self.set_len(0);
+            NonNull::from(self.parts())
+        };
+        TailRing { tail: self, buf, head: 0, len, policy }
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailRing<'a, T, V> {
+    /// Rotate the elements into logical order, and into [`TailVec`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(3);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut ring = rest.into_ring(OverflowPolicy::Overwrite);
+    /// ring.extend(1..=5);
+    ///
+    /// let mut rest = ring.into_tail();
+    /// assert_eq!(rest, [3, 4, 5]);
+    /// assert_eq!(rest.pop(), Some(5));
+    /// ```
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        let mut this = ManuallyDrop::new(self);
+        this.make_contiguous();
+        unsafe {
+            let len = this.len;
+            let mut tail = ptr::read(&this.tail);
+            ptr::drop_in_place(&mut this.policy);
+            tail.set_len(len);
+            tail
+        }
+    }
+
+    fn buf(&self) -> &[MaybeUninit<T>] {
+        unsafe { self.buf.as_ref() }
+    }
+
+    fn buf_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe { self.buf.as_mut() }
+    }
+
+    /// Physical index of logical `index`, `index` must be less than `capacity()`
+    fn wrap_index(&self, index: usize) -> usize {
+        let cap = self.capacity();
+        let index = self.head + index;
+        if index >= cap { index - cap } else { index }
+    }
+
+    /// Move the elements to the start of buffer, in logical order
+    fn make_contiguous(&mut self) {
+        let (head, len, cap) = (self.head, self.len, self.capacity());
+        self.head = 0;
+        if head == 0 || mem::size_of::<T>() == 0 {
+            return;
+        }
+
+        let buf = self.buf_mut();
+        if head + len <= cap {
+            unsafe {
+                let ptr = buf.as_mut_ptr();
+                ptr::copy(ptr.add(head), ptr, len);
+            }
+        } else {
+            buf.rotate_left(head);
+        }
+    }
+
+    /// Elements count of the ring
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the ring contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Capacity of the ring, equal [`TailVec::capacity`]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns `true` if [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailRing::len
+    /// [`capacity()`]: TailRing::capacity
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Get current [`OverflowPolicy`]
+    #[must_use]
+    pub fn policy(&self) -> &OverflowPolicy<'a, T> {
+        &self.policy
+    }
+
+    /// Replace [`OverflowPolicy`], returns the old policy
+    pub fn set_policy(&mut self, policy: OverflowPolicy<'a, T>) -> OverflowPolicy<'a, T> {
+        mem::replace(&mut self.policy, policy)
+    }
+
+    /// Push a value to the back of ring
+    ///
+    /// When the ring is full, the oldest element is dropped or passed to callback,
+    /// see [`OverflowPolicy`]
+    ///
+    /// # Errors
+    /// - [`Err`] when the ring is full and policy is [`OverflowPolicy::Reject`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut evicted = vec![];
+    /// let mut on_evict = |n| evicted.push(n);
+    ///
+    /// let mut vec = Vec::with_capacity(2);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut ring = rest.into_ring(OverflowPolicy::Reject);
+    /// assert_eq!(ring.push(1), Ok(()));
+    /// assert_eq!(ring.push(2), Ok(()));
+    /// assert_eq!(ring.push(3), Err(3));
+    ///
+    /// ring.set_policy(OverflowPolicy::Callback(&mut on_evict));
+    /// assert_eq!(ring.push(3), Ok(()));
+    /// assert_eq!(ring.push(4), Ok(()));
+    /// drop(ring);
+    ///
+    /// assert_eq!(evicted, [1, 2]);
+    /// assert_eq!(vec, [3, 4]);
+    /// ```
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if !self.is_full() {
+            let index = self.wrap_index(self.len);
+            self.buf_mut()[index].write(value);
+            self.len += 1;
+            return Ok(());
+        }
+
+        let oldest = match self.policy {
+            OverflowPolicy::Reject => return Err(value),
+            _ if self.capacity() == 0 => value,
+            _ => {
+                let head = self.head;
+                let slot = &mut self.buf_mut()[head];
+                let oldest = unsafe { slot.assume_init_read() };
+                slot.write(value);
+                self.head = self.wrap_index(1);
+                oldest
+            },
+        };
+        if let OverflowPolicy::Callback(f) = &mut self.policy {
+            f(oldest);
+        }
+        Ok(())
+    }
+
+    /// Pop the oldest element
+    ///
+    /// # Results
+    /// - [`None`] when [`len()`] by zero
+    ///
+    /// [`len()`]: TailRing::len
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let head = self.head;
+        self.head = self.wrap_index(1);
+        self.len -= 1;
+        Some(unsafe { self.buf_mut()[head].assume_init_read() })
+    }
+
+    /// Pop the newest element
+    ///
+    /// # Results
+    /// - [`None`] when [`len()`] by zero
+    ///
+    /// [`len()`]: TailRing::len
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let index = self.wrap_index(self.len);
+        Some(unsafe { self.buf_mut()[index].assume_init_read() })
+    }
+
+    /// Get element of logical `index`, zero is the oldest
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        let index = self.wrap_index(index);
+        Some(unsafe { self.buf()[index].assume_init_ref() })
+    }
+
+    /// Get mutable element of logical `index`, zero is the oldest
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        let index = self.wrap_index(index);
+        Some(unsafe { self.buf_mut()[index].assume_init_mut() })
+    }
+
+    /// Remove and drop all elements
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back): (*mut [T], *mut [T]) = (front, back);
+        self.head = 0;
+        self.len = 0;
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    fn slices_range(&self) -> (Range<usize>, Range<usize>) {
+        let cap = self.capacity();
+        let head = self.head;
+        if self.len <= cap - head {
+            (head..head + self.len, 0..0)
+        } else {
+            (head..cap, 0..self.len - (cap - head))
+        }
+    }
+
+    /// Returns a pair of slices which contain, in order, the elements of the ring
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(3);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut ring = rest.into_ring(OverflowPolicy::Overwrite);
+    /// ring.extend([1, 2]);
+    /// assert_eq!(ring.as_slices(), (&[1, 2][..], &[][..]));
+    /// ring.extend([3, 4]);
+    /// assert_eq!(ring.as_slices(), (&[2, 3][..], &[4][..]));
+    /// ```
+    #[must_use]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.slices_range();
+        let buf = self.buf();
+        unsafe {
+            (slice_assume_init(&buf[front]), slice_assume_init(&buf[back]))
+        }
+    }
+
+    /// Returns a pair of mutable slices which contain, in order, the elements of the ring
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.slices_range();
+        let (left, right) = self.buf_mut().split_at_mut(front.start);
+        unsafe {
+            (
+                slice_assume_init_mut(&mut right[..front.len()]),
+                slice_assume_init_mut(&mut left[back]),
+            )
+        }
+    }
+
+    /// Iterate the elements in logical order, from the oldest
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+
+    /// Iterate the mutable elements in logical order, from the oldest
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back)
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Drop for TailRing<'_, T, V> {
+    fn drop(&mut self) {
+        self.make_contiguous();
+        unsafe { self.tail.set_len(self.len) }
+    }
+}
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for TailRing<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Extend<T> for TailRing<'_, T, V> {
+    /// Push all elements, following the [`OverflowPolicy`]
+    ///
+    /// # Panics
+    /// Policy is [`OverflowPolicy::Reject`] and the ring is full
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|ele| {
+                assert!(self.push(ele).is_ok(),
+                        "Overflow of capacity when extend elements");
+            });
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r TailRing<'_, T, V> {
+    type Item = &'r T;
+    type IntoIter = Chain<slice::Iter<'r, T>, slice::Iter<'r, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r mut TailRing<'_, T, V> {
+    type Item = &'r mut T;
+    type IntoIter = Chain<slice::IterMut<'r, T>, slice::IterMut<'r, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + ?Sized + Send> Send for TailRing<'_, T, V> {
+}
+unsafe impl<T: Sync, V: VecLike<T = T> + ?Sized + Sync> Sync for TailRing<'_, T, V> {
+}
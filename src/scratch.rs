@@ -0,0 +1,162 @@
+use core::{
+    cell::RefCell,
+    fmt::{self, Debug},
+    mem,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{SplitTail, TailVec, VecLike};
+
+/// Borrow the spare capacity as temporary scratch [`TailVec`]
+pub trait WithScratch: VecLike + Sized {
+    /// Call `f` with an empty scratch [`TailVec`] of at least `n` capacity,
+    /// it reserves when the spare capacity is less than `n`
+    ///
+    /// The elements pushed into scratch are dropped after `f` returns or unwinds,
+    /// [`Self`] is always reset to its original length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![3, 1, 2];
+    ///
+    /// let sum = vec.with_scratch(4, |mut scratch| {
+    ///     assert!(scratch.capacity() >= 4);
+    ///     scratch.push(10).unwrap();
+    ///     scratch.push(20).unwrap();
+    ///     scratch.iter().sum::<i32>()
+    /// });
+    /// assert_eq!(sum, 30);
+    /// assert_eq!(vec, [3, 1, 2]);
+    /// ```
+    fn with_scratch<R, F>(&mut self, n: usize, f: F) -> R
+    where F: FnOnce(TailVec<'_, Self::T, Self>) -> R;
+}
+impl<T> WithScratch for Vec<T> {
+    fn with_scratch<R, F>(&mut self, n: usize, f: F) -> R
+    where F: FnOnce(TailVec<'_, T, Self>) -> R,
+    {
+        struct Guard<T> {
+            vec: *mut Vec<T>,
+            len: usize,
+        }
+        impl<T> Drop for Guard<T> {
+            fn drop(&mut self) {
+                let vec = unsafe { &mut *self.vec };
+                if vec.len() < self.len {
+                    // the scratch was leaked, the original elements are untouched
+                    unsafe { vec.set_len(self.len) }
+                } else {
+                    vec.truncate(self.len);
+                }
+            }
+        }
+
+        let len = self.len();
+        self.reserve(n);
+        let guard = Guard { vec: self, len };
+        let (_, scratch) = unsafe { &mut *guard.vec }.split_tail(len);
+        f(scratch)
+    }
+}
+
+/// Pool of recycled [`Vec`] for scratch [`TailVec`]
+///
+/// Hot loops reuse the capacity of pooled [`Vec`] instead of allocating,
+/// it can be used recursively, each nested call takes a different [`Vec`].
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let pool = ScratchPool::new();
+///
+/// for i in 0..3 {
+///     pool.with_scratch(8, |mut outer| {
+///         outer.push(i).unwrap();
+///         pool.with_scratch(8, |mut inner| {
+///             inner.push(i * 10).unwrap();
+///         });
+///         assert_eq!(outer, [i]);
+///     });
+/// }
+/// assert_eq!(pool.pooled(), 2);
+/// ```
+pub struct ScratchPool<T> {
+    vecs: RefCell<Vec<Vec<T>>>,
+}
+impl<T> ScratchPool<T> {
+    /// Create an empty pool
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { vecs: RefCell::new(Vec::new()) }
+    }
+
+    /// Count of pooled [`Vec`], they are not in use
+    #[must_use]
+    pub fn pooled(&self) -> usize {
+        self.vecs.borrow().len()
+    }
+
+    /// Add a [`Vec`] into pool, it is cleared
+    pub fn recycle(&self, mut vec: Vec<T>) {
+        vec.clear();
+        self.vecs.borrow_mut().push(vec);
+    }
+
+    /// Drop all pooled [`Vec`], and free their capacity
+    pub fn clear(&self) {
+        let vecs = mem::take(&mut *self.vecs.borrow_mut());
+        drop(vecs);
+    }
+
+    /// Take a pooled [`Vec`], the smallest one of at least `n` capacity,
+    /// or the largest one
+    fn take(&self, n: usize) -> Vec<T> {
+        let mut vecs = self.vecs.borrow_mut();
+        let best = vecs.iter()
+            .enumerate()
+            .min_by_key(|(_, vec)| {
+                let cap = vec.capacity();
+                if cap >= n { (0, cap) } else { (1, usize::MAX - cap) }
+            })
+            .map(|(i, _)| i);
+        best.map_or_else(Vec::new, |i| vecs.swap_remove(i))
+    }
+
+    /// Call `f` with an empty scratch [`TailVec`] of at least `n` capacity,
+    /// the [`Vec`] is taken from pool, and returned after `f` returns or unwinds
+    ///
+    /// *See [`WithScratch::with_scratch`] for more documents*
+    pub fn with_scratch<R, F>(&self, n: usize, f: F) -> R
+    where F: FnOnce(TailVec<'_, T>) -> R,
+    {
+        struct Return<'p, T> {
+            pool: &'p ScratchPool<T>,
+            vec: Vec<T>,
+        }
+        impl<T> Drop for Return<'_, T> {
+            fn drop(&mut self) {
+                let vec = mem::take(&mut self.vec);
+                self.pool.recycle(vec);
+            }
+        }
+
+        let mut ret = Return { pool: self, vec: self.take(n) };
+        ret.vec.with_scratch(n, f)
+    }
+}
+impl<T> Default for ScratchPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T> Debug for ScratchPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScratchPool")
+            .field("pooled", &self.pooled())
+            .finish()
+    }
+}
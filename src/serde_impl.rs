@@ -0,0 +1,126 @@
+use core::fmt;
+
+use serde::{
+    de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor},
+    ser::{Serialize, Serializer},
+    Deserialize,
+};
+
+use crate::{TailVec, VecLike};
+
+impl<T: Serialize, V: VecLike<T = T> + ?Sized> Serialize for TailVec<'_, T, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Deserialize a sequence, and append elements into tail
+    ///
+    /// # Errors
+    /// - `deserializer` returns error
+    /// - elements count greater than `capacity() - len()`
+    ///
+    /// When an error occurs, [`len()`] will not change.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// use serde::de::value::{Error, SeqDeserializer};
+    ///
+    /// let mut vec = vec![0];
+    /// vec.reserve_exact(3);
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// let de = SeqDeserializer::<_, Error>::new([1, 2].into_iter());
+    /// rest.deserialize_into(de).unwrap();
+    /// assert_eq!(rest, [1, 2]);
+    ///
+    /// let de = SeqDeserializer::<_, Error>::new([3, 4].into_iter());
+    /// assert!(rest.deserialize_into(de).is_err()); // Overflow of capacity
+    /// assert_eq!(rest, [1, 2]);
+    /// ```
+    ///
+    /// [`len()`]: TailVec::len
+    pub fn deserialize_into<'de, D>(&mut self, deserializer: D) -> Result<(), D::Error>
+    where T: Deserialize<'de>,
+          D: Deserializer<'de>,
+    {
+        DeserializeInto::new(self).deserialize(deserializer)
+    }
+}
+
+/// [`DeserializeSeed`] of append elements into [`TailVec`]
+///
+/// See [`TailVec::deserialize_into`] for more.
+pub struct DeserializeInto<'r, 'a, T, V: VecLike<T = T> + ?Sized> {
+    tail: &'r mut TailVec<'a, T, V>,
+}
+impl<'r, 'a, T, V: VecLike<T = T> + ?Sized> DeserializeInto<'r, 'a, T, V> {
+    /// Create [`DeserializeSeed`] from [`TailVec`]
+    pub fn new(tail: &'r mut TailVec<'a, T, V>) -> Self {
+        Self { tail }
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> fmt::Debug for DeserializeInto<'_, '_, T, V>
+where T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DeserializeInto").field(&self.tail).finish()
+    }
+}
+impl<'de, T, V> DeserializeSeed<'de> for DeserializeInto<'_, '_, T, V>
+where T: Deserialize<'de>,
+      V: VecLike<T = T> + ?Sized,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+impl<'de, T, V> Visitor<'de> for DeserializeInto<'_, '_, T, V>
+where T: Deserialize<'de>,
+      V: VecLike<T = T> + ?Sized,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rest = self.tail.capacity() - self.tail.len();
+        write!(f, "a sequence of at most {rest} elements")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let orig_len = self.tail.len();
+        let rest = self.tail.capacity() - orig_len;
+
+        if let Some(hint) = seq.size_hint() {
+            if hint > rest {
+                return Err(de::Error::invalid_length(hint, &OverflowOf(rest)));
+            }
+        }
+
+        loop {
+            let ele = match seq.next_element::<T>() {
+                Ok(Some(ele)) => ele,
+                Ok(None) => break Ok(()),
+                Err(e) => {
+                    self.tail.truncate(orig_len);
+                    break Err(e);
+                },
+            };
+            if self.tail.push(ele).is_err() {
+                let count = self.tail.len() - orig_len + 1;
+                self.tail.truncate(orig_len);
+                break Err(de::Error::invalid_length(count, &OverflowOf(rest)));
+            }
+        }
+    }
+}
+
+struct OverflowOf(usize);
+impl de::Expected for OverflowOf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements (overflow of capacity)", self.0)
+    }
+}
@@ -0,0 +1,499 @@
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ptr::{self, NonNull},
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{TailVec, VecLike};
+
+/// Slab with stable `usize` keys over [`TailVec`], create from [`into_slab`]
+///
+/// The slots are the [`TailVec`] capacity, it never reallocates,
+/// so keys and addresses of elements stay valid until they are removed.
+///
+/// The occupied bitmap is stored in the last few slots,
+/// one bit of each slot, so [`capacity()`] is slightly less than [`TailVec::capacity`],
+/// and [`get`], [`contains`] and [`remove`] are `O(1)`.
+///
+/// The vacated slots store a free list of their keys,
+/// so [`insert`] is `O(1)` when `T` is at least as large as `usize`,
+/// otherwise it scans the bitmap for the next vacated slot.
+///
+/// The occupied entries are compacted in key order when [`TailSlab`] is dropped,
+/// or [`into_tail`], and [`compact`] reports the key remapping.
+///
+/// # Leaking
+/// If the [`TailSlab`] goes out of scope without being dropped
+/// (due to [`mem::forget`], for example),
+/// the elements of the slab are leaked.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec!["fixed"];
+/// vec.reserve_exact(8);
+/// let (_, rest) = vec.split_tail(1);
+///
+/// let Ok(mut slab) = rest.into_slab() else { unreachable!() };
+/// let a = slab.insert("a").unwrap();
+/// let b = slab.insert("b").unwrap();
+/// let c = slab.insert("c").unwrap();
+/// assert_eq!(slab.remove(b), Some("b"));
+/// assert_eq!(slab.get(b), None);
+/// assert_eq!(slab.get(c), Some(&"c"));
+///
+/// let d = slab.insert("d").unwrap();
+/// assert_eq!(d, b); // Reuse vacated slot
+/// assert_eq!(slab.iter().collect::<Vec<_>>(), [(a, &"a"), (d, &"d"), (c, &"c")]);
+/// drop(slab);
+///
+/// assert_eq!(vec, ["fixed", "a", "d", "c"]);
+/// ```
+///
+/// [`into_slab`]: TailVec::into_slab
+/// [`capacity()`]: TailSlab::capacity
+/// [`get`]: TailSlab::get
+/// [`contains`]: TailSlab::contains
+/// [`remove`]: TailSlab::remove
+/// [`insert`]: TailSlab::insert
+/// [`into_tail`]: TailSlab::into_tail
+/// [`compact`]: TailSlab::compact
+/// [`mem::forget`]: core::mem::forget
+pub struct TailSlab<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    tail: TailVec<'a, T, V>,
+    /// Entry slots count, the bitmap is after them
+    slots: usize,
+    len: usize,
+    /// All slots from it are vacant
+    top: usize,
+    /// A vacated key before `top`, or [`NIL`]
+    ///
+    /// If `T` can store a `usize`, it is the head of free list,
+    /// otherwise it is the lowest vacated key.
+    free: usize,
+}
+
+/// End of the free list
+const NIL: usize = usize::MAX;
+
+/// Split `cap` slots into entry slots and bitmap slots, returns entry slots count
+fn slots_count(cap: usize, size: usize) -> usize {
+    // each entry slot needs `size * 8 + 1` bits
+    let mut bitmap_slots = cap / (size * 8 + 1);
+    while (cap - bitmap_slots).div_ceil(8) > bitmap_slots * size {
+        bitmap_slots += 1;
+    }
+    cap - bitmap_slots
+}
+
+/// Find next occupied key from `*key`
+fn next_occupied(bitmap: &[u8], key: &mut usize, remaining: &mut usize) -> Option<usize> {
+    if *remaining == 0 {
+        return None;
+    }
+    while bitmap[*key / 8] & (1 << (*key % 8)) == 0 {
+        *key += 1;
+    }
+    let cur = *key;
+    *key += 1;
+    *remaining -= 1;
+    Some(cur)
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into slab, the elements are kept, and their keys are the indices
+    ///
+    /// *See [`TailSlab`] for more documents*
+    ///
+    /// # Errors
+    /// - [`Err`] when the spare capacity is not enough to store the occupied bitmap
+    /// - [`Err`] when `T` is zero-sized
+    pub fn into_slab(self) -> Result<TailSlab<'a, T, V>, Self> {
+        let size = mem::size_of::<T>();
+        let len = self.len();
+        if size == 0 {
+            return Err(self);
+        }
+        let slots = slots_count(self.capacity(), size);
+        if len > slots {
+            return Err(self);
+        }
+
+        let mut slab = TailSlab { tail: self, slots, len, top: len, free: NIL };
+        let bitmap = slab.bitmap_mut();
+        bitmap.fill(0);
+        bitmap[..len / 8].fill(!0);
+        if !len.is_multiple_of(8) {
+            bitmap[len / 8] = (1 << (len % 8)) - 1;
+        }
+        Ok(slab)
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailSlab<'a, T, V> {
+    /// The vacated slots can store the free list links
+    const LINKED: bool = mem::size_of::<T>() >= mem::size_of::<usize>();
+
+    fn bitmap(&self) -> &[u8] {
+        unsafe {
+            let ptr = self.tail.as_ptr().add(self.slots);
+            slice::from_raw_parts(ptr.cast(), self.slots.div_ceil(8))
+        }
+    }
+
+    fn bitmap_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            let ptr = self.tail.as_mut_ptr().add(self.slots);
+            slice::from_raw_parts_mut(ptr.cast(), self.slots.div_ceil(8))
+        }
+    }
+
+    fn slot(&mut self, key: usize) -> *mut T {
+        unsafe { self.tail.as_mut_ptr().add(key) }
+    }
+
+    fn set_occupied(&mut self, key: usize, occupied: bool) {
+        let byte = &mut self.bitmap_mut()[key / 8];
+        if occupied {
+            *byte |= 1 << (key % 8);
+        } else {
+            *byte &= !(1 << (key % 8));
+        }
+    }
+
+    /// Next vacated key of `key` before `top`, or [`NIL`]
+    fn next_free(&mut self, key: usize) -> usize {
+        if Self::LINKED {
+            return unsafe { self.slot(key).cast::<usize>().read_unaligned() };
+        }
+        let top = self.top;
+        let bitmap = self.bitmap();
+        (key + 1..top)
+            .find(|key| bitmap[key / 8] & (1 << (key % 8)) == 0)
+            .unwrap_or(NIL)
+    }
+
+    /// Returns `true` if `key` is occupied
+    #[must_use]
+    pub fn contains(&self, key: usize) -> bool {
+        key < self.top
+            && self.bitmap()[key / 8] & (1 << (key % 8)) != 0
+    }
+
+    /// Elements count of the slab
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slab contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Slots count of the slab, all keys are less than it
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.slots
+    }
+
+    /// Insert a value into a vacant slot, and return its key
+    ///
+    /// The vacated slots are reused first,
+    /// the most recently vacated one when `T` can store a `usize`,
+    /// otherwise the lowest one.
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailSlab::len
+    /// [`capacity()`]: TailSlab::capacity
+    pub fn insert(&mut self, value: T) -> Result<usize, T> {
+        let key = if self.free != NIL {
+            let key = self.free;
+            self.free = self.next_free(key);
+            key
+        } else if self.top < self.capacity() {
+            self.top += 1;
+            self.top - 1
+        } else {
+            return Err(value);
+        };
+
+        unsafe { self.slot(key).write(value) }
+        self.set_occupied(key, true);
+        self.len += 1;
+        Ok(key)
+    }
+
+    /// Remove the value of `key`, and return it
+    pub fn remove(&mut self, key: usize) -> Option<T> {
+        if !self.contains(key) {
+            return None;
+        }
+        self.set_occupied(key, false);
+        self.len -= 1;
+        let value = unsafe { self.slot(key).read() };
+
+        if Self::LINKED {
+            let next = self.free;
+            unsafe { self.slot(key).cast::<usize>().write_unaligned(next) }
+            self.free = key;
+        } else if self.free == NIL || key < self.free {
+            self.free = key;
+        }
+        Some(value)
+    }
+
+    /// Get the value of `key`
+    #[must_use]
+    pub fn get(&self, key: usize) -> Option<&T> {
+        if !self.contains(key) {
+            return None;
+        }
+        Some(unsafe { &*self.tail.as_ptr().add(key) })
+    }
+
+    /// Get the mutable value of `key`
+    pub fn get_mut(&mut self, key: usize) -> Option<&mut T> {
+        if !self.contains(key) {
+            return None;
+        }
+        Some(unsafe { &mut *self.slot(key) })
+    }
+
+    /// Remove and drop all elements
+    pub fn clear(&mut self) {
+        let mut key = 0;
+        let mut remaining = mem::take(&mut self.len);
+        self.top = 0;
+        self.free = NIL;
+
+        let slots = self.tail.as_mut_ptr();
+        while let Some(key) = next_occupied(self.bitmap(), &mut key, &mut remaining) {
+            self.set_occupied(key, false);
+            unsafe { ptr::drop_in_place(slots.add(key)) }
+        }
+    }
+
+    /// Iterate the `(key, &value)` in key order
+    pub fn iter(&self) -> SlabIter<'_, T> {
+        SlabIter {
+            slots: NonNull::new(self.tail.as_ptr().cast_mut()).unwrap(),
+            bitmap: self.bitmap(),
+            key: 0,
+            remaining: self.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterate the `(key, &mut value)` in key order
+    pub fn iter_mut(&mut self) -> SlabIterMut<'_, T> {
+        let slots = NonNull::new(self.tail.as_mut_ptr()).unwrap();
+        SlabIterMut {
+            slots,
+            bitmap: self.bitmap(),
+            key: 0,
+            remaining: self.len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Compact the occupied entries in key order, and into [`TailVec`]
+    ///
+    /// `remap(old_key, new_index)` is called for each moved element
+    ///
+    /// If `remap` panics, the moved elements are kept, the others are dropped
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![0, 1, 2, 3];
+    /// vec.reserve_exact(1);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let Ok(mut slab) = rest.into_slab() else { unreachable!() };
+    /// slab.remove(1);
+    ///
+    /// let mut moves = vec![];
+    /// let rest = slab.compact(|old, new| moves.push((old, new)));
+    /// assert_eq!(rest, [0, 2, 3]);
+    /// assert_eq!(moves, [(2, 1), (3, 2)]);
+    /// ```
+    pub fn compact<F>(mut self, mut remap: F) -> TailVec<'a, T, V>
+    where F: FnMut(usize, usize),
+    {
+        let len = self.compact_in_place(&mut remap);
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let mut tail = ptr::read(&this.tail);
+            tail.set_len(len);
+            tail
+        }
+    }
+
+    /// Compact the occupied entries in key order, and into [`TailVec`]
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        self.compact(|_, _| ())
+    }
+
+    fn compact_in_place(&mut self, remap: &mut dyn FnMut(usize, usize)) -> usize {
+        struct Guard<'g, 'a, T, V: VecLike<T = T> + ?Sized> {
+            slab: &'g mut TailSlab<'a, T, V>,
+            key: usize,
+            new_index: usize,
+        }
+        impl<T, V: VecLike<T = T> + ?Sized> Drop for Guard<'_, '_, T, V> {
+            fn drop(&mut self) {
+                // When unwinding from `remap`, drop the elements not yet moved
+                let slab = &mut *self.slab;
+                let mut remaining = slab.len - self.new_index;
+                let slots = slab.tail.as_mut_ptr();
+                while let Some(key) = next_occupied(slab.bitmap(), &mut self.key, &mut remaining) {
+                    slab.set_occupied(key, false);
+                    unsafe { ptr::drop_in_place(slots.add(key)) }
+                }
+                slab.len = self.new_index;
+                slab.top = self.new_index;
+                slab.free = NIL;
+            }
+        }
+
+        let len = self.len;
+        let mut guard = Guard { slab: self, key: 0, new_index: 0 };
+        let slots = guard.slab.tail.as_mut_ptr();
+        let mut remaining = len;
+        while let Some(key) = next_occupied(guard.slab.bitmap(), &mut guard.key, &mut remaining) {
+            let new_index = guard.new_index;
+            if key != new_index {
+                guard.slab.set_occupied(key, false);
+                guard.slab.set_occupied(new_index, true);
+                unsafe { ptr::copy_nonoverlapping(slots.add(key), slots.add(new_index), 1) }
+            }
+            guard.new_index += 1;
+            if key != new_index {
+                remap(key, new_index);
+            }
+        }
+        len
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Drop for TailSlab<'_, T, V> {
+    fn drop(&mut self) {
+        let len = self.compact_in_place(&mut |_, _| ());
+        unsafe { self.tail.set_len(len) }
+    }
+}
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for TailSlab<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r TailSlab<'_, T, V> {
+    type Item = (usize, &'r T);
+    type IntoIter = SlabIter<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r mut TailSlab<'_, T, V> {
+    type Item = (usize, &'r mut T);
+    type IntoIter = SlabIterMut<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + ?Sized + Send> Send for TailSlab<'_, T, V> {
+}
+unsafe impl<T: Sync, V: VecLike<T = T> + ?Sized + Sync> Sync for TailSlab<'_, T, V> {
+}
+
+/// Iterator of [`TailSlab::iter`]
+pub struct SlabIter<'r, T> {
+    slots: NonNull<T>,
+    bitmap: &'r [u8],
+    key: usize,
+    remaining: usize,
+    _phantom: PhantomData<&'r T>,
+}
+impl<'r, T> Iterator for SlabIter<'r, T> {
+    type Item = (usize, &'r T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = next_occupied(self.bitmap, &mut self.key, &mut self.remaining)?;
+        Some((key, unsafe { self.slots.add(key).as_ref() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> ExactSizeIterator for SlabIter<'_, T> {
+}
+impl<T> FusedIterator for SlabIter<'_, T> {
+}
+impl<T> Clone for SlabIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+unsafe impl<T: Sync> Send for SlabIter<'_, T> {
+}
+unsafe impl<T: Sync> Sync for SlabIter<'_, T> {
+}
+impl<T: Debug> Debug for SlabIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.clone()).finish()
+    }
+}
+
+/// Iterator of [`TailSlab::iter_mut`]
+pub struct SlabIterMut<'r, T> {
+    slots: NonNull<T>,
+    bitmap: &'r [u8],
+    key: usize,
+    remaining: usize,
+    _phantom: PhantomData<&'r mut T>,
+}
+impl<'r, T> Iterator for SlabIterMut<'r, T> {
+    type Item = (usize, &'r mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = next_occupied(self.bitmap, &mut self.key, &mut self.remaining)?;
+        Some((key, unsafe { self.slots.add(key).as_mut() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+impl<T> ExactSizeIterator for SlabIterMut<'_, T> {
+}
+impl<T> FusedIterator for SlabIterMut<'_, T> {
+}
+unsafe impl<T: Send> Send for SlabIterMut<'_, T> {
+}
+unsafe impl<T: Sync> Sync for SlabIterMut<'_, T> {
+}
+impl<T> Debug for SlabIterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlabIterMut")
+            .field("remaining", &self.remaining)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,381 @@
+use core::{cmp::Ordering, ptr};
+
+use crate::{tailvec::slice_assume_init_mut, utils::{self, SplitSlice}, TailVec, VecLike};
+
+impl<T, V: VecLike<T = T> + ?Sized> TailVec<'_, T, V> {
+    /// Scratch slots count of the merge buffer required by
+    /// [`try_sort_stable_by_in_spare`], it is `len() / 2`
+    ///
+    /// [`try_sort_stable_by_in_spare`]: TailVec::try_sort_stable_by_in_spare
+    #[must_use]
+    pub fn sort_scratch_len(&self) -> usize {
+        self.len() / 2
+    }
+
+    /// Stable sort by [`Ord`], without allocating
+    ///
+    /// Without enough spare capacity it falls back to the slower in place merge,
+    /// use [`try_sort_stable_in_spare`] to reject it.
+    ///
+    /// *See [`sort_stable_by_in_spare`] for more documents*
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![9, 5, 1, 4, 2, 3];
+    /// vec.reserve_exact(2);
+    /// let (_, mut rest) = vec.split_tail(1);
+    ///
+    /// rest.sort_stable_in_spare();
+    /// assert_eq!(rest, [1, 2, 3, 4, 5]);
+    /// ```
+    ///
+    /// [`sort_stable_by_in_spare`]: TailVec::sort_stable_by_in_spare
+    /// [`try_sort_stable_in_spare`]: TailVec::try_sort_stable_in_spare
+    pub fn sort_stable_in_spare(&mut self)
+    where T: Ord,
+    {
+        self.sort_stable_by_in_spare(T::cmp);
+    }
+
+    /// Stable sort by key function, without allocating
+    ///
+    /// Without enough spare capacity it falls back to the slower in place merge,
+    /// use [`try_sort_by_key_in_spare`] to reject it.
+    ///
+    /// *See [`sort_stable_by_in_spare`] for more documents*
+    ///
+    /// [`sort_stable_by_in_spare`]: TailVec::sort_stable_by_in_spare
+    /// [`try_sort_by_key_in_spare`]: TailVec::try_sort_by_key_in_spare
+    pub fn sort_by_key_in_spare<K, F>(&mut self, mut f: F)
+    where F: FnMut(&T) -> K,
+          K: Ord,
+    {
+        self.sort_stable_by_in_spare(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Stable sort by [`Ord`], without allocating and without in place fallback
+    ///
+    /// *See [`try_sort_stable_by_in_spare`] for more documents*
+    ///
+    /// # Errors
+    /// - [`Err`] when the spare capacity is less than [`sort_scratch_len()`],
+    ///   then the elements are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![3, 1, 2, 0];
+    /// let (_, mut rest) = vec.split_tail(0);
+    /// assert_eq!(rest.try_sort_stable_in_spare(), Err(()));
+    /// drop(rest);
+    ///
+    /// vec.reserve_exact(2);
+    /// let (_, mut rest) = vec.split_tail(0);
+    /// assert_eq!(rest.try_sort_stable_in_spare(), Ok(()));
+    /// assert_eq!(rest, [0, 1, 2, 3]);
+    /// ```
+    ///
+    /// [`try_sort_stable_by_in_spare`]: TailVec::try_sort_stable_by_in_spare
+    /// [`sort_scratch_len()`]: TailVec::sort_scratch_len
+    #[allow(clippy::result_unit_err)]
+    pub fn try_sort_stable_in_spare(&mut self) -> Result<(), ()>
+    where T: Ord,
+    {
+        self.try_sort_stable_by_in_spare(T::cmp)
+            .map_err(drop)
+    }
+
+    /// Stable sort by key function, without allocating and without in place fallback
+    ///
+    /// *See [`try_sort_stable_by_in_spare`] for more documents*
+    ///
+    /// # Errors
+    /// - [`Err`] with `f` when the spare capacity is less than [`sort_scratch_len()`],
+    ///   then the elements are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+    /// vec.reserve_exact(2);
+    /// let (_, mut rest) = vec.split_tail(0);
+    ///
+    /// assert!(rest.try_sort_by_key_in_spare(|&(k, _)| k).is_ok());
+    /// assert_eq!(rest, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    /// ```
+    ///
+    /// [`try_sort_stable_by_in_spare`]: TailVec::try_sort_stable_by_in_spare
+    /// [`sort_scratch_len()`]: TailVec::sort_scratch_len
+    pub fn try_sort_by_key_in_spare<K, F>(&mut self, mut f: F) -> Result<(), F>
+    where F: FnMut(&T) -> K,
+          K: Ord,
+    {
+        if self.capacity() - self.len() < self.sort_scratch_len() {
+            return Err(f);
+        }
+        self.sort_stable_by_in_spare(|a, b| f(a).cmp(&f(b)));
+        Ok(())
+    }
+
+    /// Stable merge sort by comparator,
+    /// the spare capacity after [`len()`] is the merge buffer, no allocation
+    ///
+    /// It is `O(n log n)` time when the spare capacity is at least [`sort_scratch_len()`].
+    /// Otherwise the runs that cannot be buffered are merged by in place stable algorithm,
+    /// it is `O(n log² n)` time in the worst case (no spare capacity),
+    /// use [`try_sort_stable_by_in_spare`] to reject it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+    /// let (_, mut rest) = vec.split_tail(0);
+    /// assert_eq!(rest.spare_capacity_mut().len(), 0); // Fallback to in place
+    ///
+    /// rest.sort_stable_by_in_spare(|a, b| a.0.cmp(&b.0));
+    /// assert_eq!(rest, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+    /// ```
+    ///
+    /// [`len()`]: TailVec::len
+    /// [`sort_scratch_len()`]: TailVec::sort_scratch_len
+    /// [`try_sort_stable_by_in_spare`]: TailVec::try_sort_stable_by_in_spare
+    pub fn sort_stable_by_in_spare<F>(&mut self, mut cmp: F)
+    where F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = self.len();
+        let (v, buf) = unsafe { self.parts().split_at_mut(len) };
+        let v = unsafe { slice_assume_init_mut(v) };
+        utils::stable_sort_by_buf(v, buf, &mut cmp);
+    }
+
+    /// Stable merge sort by comparator,
+    /// the spare capacity after [`len()`] is the merge buffer, no allocation
+    ///
+    /// # Errors
+    /// - [`Err`] with `cmp` when the spare capacity is less than [`sort_scratch_len()`],
+    ///   then the elements are not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![3, 1, 2, 0];
+    /// let (_, mut rest) = vec.split_tail(0);
+    /// assert!(rest.try_sort_stable_by_in_spare(i32::cmp).is_err());
+    /// assert_eq!(rest, [3, 1, 2, 0]);
+    /// drop(rest);
+    ///
+    /// vec.reserve_exact(2);
+    /// let (_, mut rest) = vec.split_tail(0);
+    /// assert!(rest.try_sort_stable_by_in_spare(i32::cmp).is_ok());
+    /// assert_eq!(rest, [0, 1, 2, 3]);
+    /// ```
+    ///
+    /// [`len()`]: TailVec::len
+    /// [`sort_scratch_len()`]: TailVec::sort_scratch_len
+    pub fn try_sort_stable_by_in_spare<F>(&mut self, cmp: F) -> Result<(), F>
+    where F: FnMut(&T, &T) -> Ordering,
+    {
+        if self.capacity() - self.len() < self.sort_scratch_len() {
+            return Err(cmp);
+        }
+        self.sort_stable_by_in_spare(cmp);
+        Ok(())
+    }
+
+    /// Merge the tail into the sorted `head` as a single sorted run,
+    /// and drop the split, returns the merged length
+    ///
+    /// *See [`merge_into_head_by`] for more documents*
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 4, 7];
+    /// vec.reserve_exact(4);
+    /// let (head, mut rest) = vec.split_tail(3);
+    /// (&mut rest).extend([5, 0, 4]);
+    ///
+    /// assert_eq!(rest.merge_into_head(head), 6);
+    /// assert_eq!(vec, [0, 1, 4, 4, 5, 7]);
+    /// ```
+    ///
+    /// [`merge_into_head_by`]: TailVec::merge_into_head_by
+    #[track_caller]
+    pub fn merge_into_head(self, head: &mut [T]) -> usize
+    where T: Ord,
+    {
+        self.merge_into_head_by(head, T::cmp)
+    }
+
+    /// Merge the tail into the sorted `head` by key function,
+    /// and drop the split, returns the merged length
+    ///
+    /// *See [`merge_into_head_by`] for more documents*
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// [`merge_into_head_by`]: TailVec::merge_into_head_by
+    #[track_caller]
+    pub fn merge_into_head_by_key<K, F>(self, head: &mut [T], mut f: F) -> usize
+    where F: FnMut(&T) -> K,
+          K: Ord,
+    {
+        self.merge_into_head_by(head, |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Merge the tail into the sorted `head` by comparator,
+    /// and drop the split, returns the merged length
+    ///
+    /// The tail is sorted stably first, elements of `head`
+    /// stay before the equal elements of tail.
+    /// The spare capacity is the merge buffer, see [`sort_stable_by_in_spare`].
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(1, "old"), (3, "old")];
+    /// vec.reserve_exact(3);
+    /// let (head, mut rest) = vec.split_tail(2);
+    /// (&mut rest).extend([(3, "new"), (2, "new"), (1, "new")]);
+    ///
+    /// assert_eq!(rest.merge_into_head_by(head, |a, b| a.0.cmp(&b.0)), 5);
+    /// assert_eq!(vec, [(1, "old"), (1, "new"), (2, "new"), (3, "old"), (3, "new")]);
+    /// ```
+    ///
+    /// [`sort_stable_by_in_spare`]: TailVec::sort_stable_by_in_spare
+    #[track_caller]
+    pub fn merge_into_head_by<F>(self, head: &mut [T], cmp: F) -> usize
+    where F: FnMut(&T, &T) -> Ordering,
+    {
+        self.merge_into_head_impl(head, false, cmp)
+    }
+
+    /// Like [`merge_into_head_by`], but the tail elements equal to
+    /// an element of `head` or an earlier tail element are dropped
+    ///
+    /// The elements of `head` are never dropped.
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(1, "old"), (3, "old")];
+    /// vec.reserve_exact(4);
+    /// let (head, mut rest) = vec.split_tail(2);
+    /// (&mut rest).extend([(3, "new"), (2, "new"), (1, "new"), (2, "newer")]);
+    ///
+    /// assert_eq!(rest.merge_into_head_dedup_by(head, |a, b| a.0.cmp(&b.0)), 3);
+    /// assert_eq!(vec, [(1, "old"), (2, "new"), (3, "old")]);
+    /// ```
+    ///
+    /// [`merge_into_head_by`]: TailVec::merge_into_head_by
+    #[track_caller]
+    pub fn merge_into_head_dedup_by<F>(self, head: &mut [T], cmp: F) -> usize
+    where F: FnMut(&T, &T) -> Ordering,
+    {
+        self.merge_into_head_impl(head, true, cmp)
+    }
+
+    /// Like [`merge_into_head`], but the tail elements equal to
+    /// an element of `head` or an earlier tail element are dropped
+    ///
+    /// *See [`merge_into_head_dedup_by`] for more documents*
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 4, 7];
+    /// vec.reserve_exact(4);
+    /// let (head, mut rest) = vec.split_tail(3);
+    /// (&mut rest).extend([5, 0, 4, 5]);
+    ///
+    /// assert_eq!(rest.merge_into_head_dedup(head), 5);
+    /// assert_eq!(vec, [0, 1, 4, 5, 7]);
+    /// ```
+    ///
+    /// [`merge_into_head`]: TailVec::merge_into_head
+    /// [`merge_into_head_dedup_by`]: TailVec::merge_into_head_dedup_by
+    #[track_caller]
+    pub fn merge_into_head_dedup(self, head: &mut [T]) -> usize
+    where T: Ord,
+    {
+        self.merge_into_head_dedup_by(head, T::cmp)
+    }
+
+    /// Like [`merge_into_head_by_key`], but the tail elements whose key equal to
+    /// the key of an element of `head` or an earlier tail element are dropped
+    ///
+    /// *See [`merge_into_head_dedup_by`] for more documents*
+    ///
+    /// # Panics
+    /// - `head` is not the head slice of this split
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(1, "old"), (3, "old")];
+    /// vec.reserve_exact(3);
+    /// let (head, mut rest) = vec.split_tail(2);
+    /// (&mut rest).extend([(2, "new"), (3, "new"), (2, "newer")]);
+    ///
+    /// assert_eq!(rest.merge_into_head_dedup_by_key(head, |&(k, _)| k), 3);
+    /// assert_eq!(vec, [(1, "old"), (2, "new"), (3, "old")]);
+    /// ```
+    ///
+    /// [`merge_into_head_by_key`]: TailVec::merge_into_head_by_key
+    /// [`merge_into_head_dedup_by`]: TailVec::merge_into_head_dedup_by
+    #[track_caller]
+    pub fn merge_into_head_dedup_by_key<K, F>(self, head: &mut [T], mut f: F) -> usize
+    where F: FnMut(&T) -> K,
+          K: Ord,
+    {
+        self.merge_into_head_dedup_by(head, |a, b| f(a).cmp(&f(b)))
+    }
+
+    #[track_caller]
+    fn merge_into_head_impl<F>(mut self, head: &mut [T], dedup: bool, mut cmp: F) -> usize
+    where F: FnMut(&T, &T) -> Ordering,
+    {
+        let mid = self.split_point();
+        assert!(head.len() == mid && ptr::eq(head.as_ptr().wrapping_add(mid), self.as_ptr()),
+                "head is not the head slice of this split");
+
+        self.sort_stable_by_in_spare(&mut cmp);
+
+        if dedup {
+            let tail = self.as_slice_mut();
+            let mut kept = 0;
+            for i in 0..tail.len() {
+                let dup = kept != 0 && cmp(&tail[kept - 1], &tail[i]) == Ordering::Equal
+                    || head.binary_search_by(|ele| cmp(ele, &tail[i])).is_ok();
+                if !dup {
+                    tail.swap(kept, i);
+                    kept += 1;
+                }
+            }
+            self.truncate(kept);
+        }
+
+        // Write the head only through `head`, and the tail only through parts
+        let tail_len = self.len();
+        unsafe {
+            let (tail, buf) = self.parts().split_at_mut(tail_len);
+            let data = SplitSlice::new(head, tail.as_mut_ptr().cast(), tail_len);
+            utils::merge_split_with_buf(data, mid, buf, &mut cmp);
+        }
+        mid + tail_len
+    }
+}
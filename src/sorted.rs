@@ -0,0 +1,352 @@
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    ops::{Bound, Deref, RangeBounds},
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{utils, TailVec, VecLike};
+
+/// Sorted [`TailVec`] by a comparator, create from [`into_sorted_tail`]
+///
+/// Insertions find the position by binary search, and shift once,
+/// equal elements keep the insertion order.
+///
+/// The elements are in sorted order when [`SortedTailVec`] is dropped.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec![9, 8, 3, 1];
+/// vec.reserve_exact(2);
+/// let (unsorted, rest) = vec.split_tail(2);
+/// assert_eq!(unsorted, [9, 8]);
+///
+/// let mut sorted = rest.into_sorted_tail();
+/// assert_eq!(sorted.insert_sorted(2), Ok(1));
+/// assert_eq!(sorted.insert_unique(3), Ok(2)); // Already exists
+/// assert_eq!(sorted.insert_sorted(0), Ok(0));
+/// assert_eq!(sorted.insert_sorted(4), Err(4)); // Overflow of capacity
+/// assert_eq!(sorted, [0, 1, 2, 3]);
+///
+/// assert!(sorted.contains(&2));
+/// assert_eq!(sorted.range(1..3), [1, 2]);
+/// assert_eq!(sorted.remove_value(&1), Some(1));
+/// drop(sorted);
+///
+/// assert_eq!(vec, [9, 8, 0, 2, 3]);
+/// ```
+///
+/// [`into_sorted_tail`]: TailVec::into_sorted_tail
+pub struct SortedTailVec<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+    F = fn(&T, &T) -> Ordering,
+> {
+    tail: TailVec<'a, T, V>,
+    cmp: F,
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into sorted tail by [`Ord`], the elements are sorted stably
+    ///
+    /// The sort is in place without buffer, it is `O(n log² n)` time.
+    ///
+    /// *See [`SortedTailVec`] for more documents*
+    #[must_use]
+    pub fn into_sorted_tail(self) -> SortedTailVec<'a, T, V>
+    where T: Ord,
+    {
+        self.into_sorted_tail_by(T::cmp as fn(&T, &T) -> Ordering)
+    }
+
+    /// Into sorted tail by comparator, the elements are sorted stably
+    ///
+    /// The sort is in place without buffer, it is `O(n log² n)` time.
+    ///
+    /// *See [`SortedTailVec`] for more documents*
+    #[must_use]
+    pub fn into_sorted_tail_by<F>(mut self, mut cmp: F) -> SortedTailVec<'a, T, V, F>
+    where F: Fn(&T, &T) -> Ordering,
+    {
+        utils::stable_sort_by(self.as_slice_mut(), &mut cmp);
+        SortedTailVec { tail: self, cmp }
+    }
+
+    /// Into sorted tail by key function, the elements are sorted stably
+    ///
+    /// The sort is in place without buffer, it is `O(n log² n)` time.
+    ///
+    /// *See [`SortedTailVec`] for more documents*
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![(3, 'a'), (1, 'b')];
+    /// vec.reserve_exact(2);
+    /// let (_, rest) = vec.split_tail(0);
+    ///
+    /// let mut sorted = rest.into_sorted_tail_by_key(|&(k, _)| k);
+    /// assert_eq!(sorted.insert_sorted((1, 'c')), Ok(1));
+    /// assert_eq!(sorted.insert_sorted((2, 'd')), Ok(2));
+    /// assert_eq!(sorted, [(1, 'b'), (1, 'c'), (2, 'd'), (3, 'a')]);
+    /// ```
+    #[must_use]
+    pub fn into_sorted_tail_by_key<K, F>(self, f: F)
+    -> SortedTailVec<'a, T, V, impl Fn(&T, &T) -> Ordering>
+    where F: Fn(&T) -> K,
+          K: Ord,
+    {
+        self.into_sorted_tail_by(move |a, b| f(a).cmp(&f(b)))
+    }
+}
+
+impl<'a, T, V, F> SortedTailVec<'a, T, V, F>
+where V: VecLike<T = T> + ?Sized,
+      F: Fn(&T, &T) -> Ordering,
+{
+    /// Into [`TailVec`], the elements are in sorted order
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        self.tail
+    }
+
+    /// Elements count
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.len()
+    }
+
+    /// Returns `true` if it contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tail.is_empty()
+    }
+
+    /// Capacity, equal [`TailVec::capacity`]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.tail.capacity()
+    }
+
+    /// Get sorted slice
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.tail.as_slice()
+    }
+
+    /// Binary search the value, like [`slice::binary_search`]
+    ///
+    /// # Errors
+    /// - [`Err`] with the insertion index when the value is not found
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize> {
+        self.as_slice().binary_search_by(|ele| (self.cmp)(ele, value))
+    }
+
+    /// Returns `true` if it contains an element equal to `value`
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.binary_search(value).is_ok()
+    }
+
+    fn upper_bound(&self, value: &T) -> usize {
+        self.as_slice()
+            .partition_point(|ele| (self.cmp)(ele, value) != Ordering::Greater)
+    }
+
+    fn lower_bound(&self, value: &T) -> usize {
+        self.as_slice()
+            .partition_point(|ele| (self.cmp)(ele, value) == Ordering::Less)
+    }
+
+    /// Insert a value in sorted position, after the equal elements,
+    /// and return the index
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: SortedTailVec::len
+    /// [`capacity()`]: SortedTailVec::capacity
+    pub fn insert_sorted(&mut self, value: T) -> Result<usize, T> {
+        let index = self.upper_bound(&value);
+        self.tail.insert(index, value)?;
+        Ok(index)
+    }
+
+    /// Insert a value in sorted position, if no equal element exists
+    ///
+    /// Returns the index of inserted value,
+    /// or the index of the equal element, then `value` is dropped.
+    ///
+    /// # Errors
+    /// - [`Err`] when no equal element exists, and [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: SortedTailVec::len
+    /// [`capacity()`]: SortedTailVec::capacity
+    pub fn insert_unique(&mut self, value: T) -> Result<usize, T> {
+        match self.binary_search(&value) {
+            Ok(index) => Ok(index),
+            Err(index) => {
+                self.tail.insert(index, value)?;
+                Ok(index)
+            },
+        }
+    }
+
+    /// Remove an element equal to `value`, and return it
+    pub fn remove_value(&mut self, value: &T) -> Option<T> {
+        let index = self.binary_search(value).ok()?;
+        Some(self.tail.remove(index))
+    }
+
+    /// Remove and return element of index
+    ///
+    /// # Panics
+    /// - `index` greater than or equal [`len()`]
+    ///
+    /// [`len()`]: SortedTailVec::len
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> T {
+        self.tail.remove(index)
+    }
+
+    /// Pop the greatest element
+    pub fn pop(&mut self) -> Option<T> {
+        self.tail.pop()
+    }
+
+    /// Shortens, keeping the first `len` elements, and dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.tail.truncate(len);
+    }
+
+    /// Remove and drop all elements
+    pub fn clear(&mut self) {
+        self.tail.clear();
+    }
+
+    /// Get the elements in range of values
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 3, 3, 5, 7];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let sorted = rest.into_sorted_tail();
+    ///
+    /// assert_eq!(sorted.range(3..7), [3, 3, 5]);
+    /// assert_eq!(sorted.range(3..=7), [3, 3, 5, 7]);
+    /// assert_eq!(sorted.range(4..), [5, 7]);
+    /// assert_eq!(sorted.range(..), [1, 3, 3, 5, 7]);
+    /// assert_eq!(sorted.range(6..2), []);
+    /// ```
+    pub fn range<R>(&self, range: R) -> &[T]
+    where R: RangeBounds<T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(value) => self.lower_bound(value),
+            Bound::Excluded(value) => self.upper_bound(value),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(value) => self.upper_bound(value),
+            Bound::Excluded(value) => self.lower_bound(value),
+            Bound::Unbounded => self.len(),
+        };
+        &self.as_slice()[start..end.max(start)]
+    }
+
+    /// Insert all elements of `iter`, and merge them in place
+    ///
+    /// It is faster than [`insert_sorted`] each element for large batch,
+    /// equal elements keep the insertion order.
+    ///
+    /// The `m` new elements are sorted in `O(m log² m)` time,
+    /// then merged without buffer in `O(n log n)` time.
+    ///
+    /// # Errors
+    /// - [`Err`] with the first element that cannot be pushed,
+    ///   then the elements before it are merged
+    ///   and the elements after it are not consumed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![2, 4, 6];
+    /// vec.reserve_exact(4);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut sorted = rest.into_sorted_tail();
+    ///
+    /// assert_eq!(sorted.merge_from([5, 1, 3]), Ok(()));
+    /// assert_eq!(sorted, [1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(sorted.merge_from([0, 9, 8]), Err(9));
+    /// assert_eq!(sorted, [0, 1, 2, 3, 4, 5, 6]);
+    /// ```
+    ///
+    /// [`insert_sorted`]: SortedTailVec::insert_sorted
+    pub fn merge_from<I>(&mut self, iter: I) -> Result<(), T>
+    where I: IntoIterator<Item = T>,
+    {
+        let mid = self.len();
+        let res = iter.into_iter()
+            .try_for_each(|ele| self.tail.push(ele));
+
+        let new = &mut self.tail.as_slice_mut()[mid..];
+        utils::stable_sort_by(new, &mut &self.cmp);
+        utils::merge_in_place(self.tail.as_slice_mut(), mid, &mut &self.cmp);
+        res
+    }
+
+    /// Iterate the elements in sorted order
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.tail.iter()
+    }
+}
+impl<T, V, F> Deref for SortedTailVec<'_, T, V, F>
+where V: VecLike<T = T> + ?Sized,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.tail.as_slice()
+    }
+}
+impl<T: Debug, V, F> Debug for SortedTailVec<'_, T, V, F>
+where V: VecLike<T = T> + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tail.fmt(f)
+    }
+}
+impl<T, U, V, F, const N: usize> PartialEq<[U; N]> for SortedTailVec<'_, T, V, F>
+where T: PartialEq<U>,
+      V: VecLike<T = T> + ?Sized,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.tail.as_slice() == other
+    }
+}
+impl<T, U, V, F> PartialEq<[U]> for SortedTailVec<'_, T, V, F>
+where T: PartialEq<U>,
+      V: VecLike<T = T> + ?Sized,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.tail.as_slice() == other
+    }
+}
+impl<'r, T, V, F> IntoIterator for &'r SortedTailVec<'_, T, V, F>
+where V: VecLike<T = T> + ?Sized,
+{
+    type Item = &'r T;
+    type IntoIter = slice::Iter<'r, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tail.iter()
+    }
+}
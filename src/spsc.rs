@@ -0,0 +1,350 @@
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+    slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::VecLike;
+
+/// Split spare capacity to single-producer single-consumer ring buffer
+pub trait SplitSpsc: VecLike + Sized {
+    /// Split spare capacity to single-producer single-consumer ring buffer
+    ///
+    /// The [`Producer`] pushes into the spare capacity,
+    /// the [`Consumer`] pops from the front of the pushed elements,
+    /// it never allocates.
+    ///
+    /// The ring state is stored at the end of spare capacity,
+    /// the remaining slots before it are the ring,
+    /// so the ring capacity is less than `capacity() - len()`.
+    ///
+    /// The initialized elements are not changed,
+    /// the remaining elements of the ring are appended to [`Self`] contiguously,
+    /// when both [`Producer`] and [`Consumer`] are dropped.
+    ///
+    /// Returns [`None`] when the spare capacity cannot hold the ring state,
+    /// or the element is zero-sized.
+    ///
+    /// # Leaking
+    /// If the [`Producer`] or [`Consumer`] goes out of scope without being dropped
+    /// (due to [`mem::forget`], for example),
+    /// the elements of the ring are leaked.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::<u64>::with_capacity(16);
+    /// vec.push(0);
+    ///
+    /// let (mut tx, mut rx) = vec.split_spsc().unwrap();
+    /// assert!(tx.capacity() >= 2);
+    /// std::thread::scope(|s| {
+    ///     s.spawn(move || {
+    ///         for i in 1..=100 {
+    ///             while tx.push(i).is_err() {
+    ///                 std::hint::spin_loop();
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     let mut expected = 1;
+    ///     while expected <= 98 {
+    ///         if let Some(n) = rx.pop() {
+    ///             assert_eq!(n, expected);
+    ///             expected += 1;
+    ///         }
+    ///     }
+    /// });
+    /// drop(rx);
+    ///
+    /// assert_eq!(vec, [0, 99, 100]);
+    /// ```
+    ///
+    /// [`Self`]: SplitSpsc
+    /// [`mem::forget`]: core::mem::forget
+    #[allow(clippy::type_complexity)]
+    fn split_spsc(&mut self) -> Option<(
+        Producer<'_, Self::T, Self>,
+        Consumer<'_, Self::T, Self>,
+    )>;
+}
+impl<V: VecLike> SplitSpsc for V {
+    #[allow(clippy::type_complexity)]
+    fn split_spsc(&mut self) -> Option<(
+        Producer<'_, Self::T, Self>,
+        Consumer<'_, Self::T, Self>,
+    )> {
+        let vec_len = self.len();
+        let mut vec = NonNull::from(self);
+        // Do not access `vec` again before drop, it may contain the data inline
+        let (spare, len_ptr) = unsafe {
+            vec.as_mut().spare_capacity_and_len_mut()
+        };
+        let spare_len = spare.len();
+        let buf = NonNull::from(spare).cast::<V::T>();
+        let (cap, shared) = Shared::<V>::place(buf, spare_len)?;
+        unsafe {
+            shared.write(Shared {
+                buf,
+                cap,
+                head: AtomicUsize::new(0),
+                tail: AtomicUsize::new(0),
+                handles: AtomicUsize::new(2),
+                vec,
+                len_ptr,
+                vec_len,
+            });
+        }
+
+        let producer = Producer {
+            shared,
+            _phantom: PhantomData,
+        };
+        let consumer = Consumer {
+            shared,
+            _phantom: PhantomData,
+        };
+        Some((producer, consumer))
+    }
+}
+
+/// The state shared by [`Producer`] and [`Consumer`],
+/// it is stored at the end of spare capacity
+///
+/// `head` and `tail` are counters modulo `2 * cap`,
+/// so a full ring and an empty ring are different for any `cap`,
+/// the elements are at `head..tail` modulo `cap`
+struct Shared<V: VecLike> {
+    buf: NonNull<V::T>,
+    cap: usize,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    /// Count of alive [`Producer`] and [`Consumer`]
+    handles: AtomicUsize,
+    vec: NonNull<V>,
+    len_ptr: Option<NonNull<usize>>,
+    vec_len: usize,
+}
+impl<V: VecLike> Shared<V> {
+    /// Place the state at the end of `buf[..len]`, returns ring capacity and the state
+    fn place(buf: NonNull<V::T>, len: usize) -> Option<(usize, NonNull<Self>)> {
+        let size = mem::size_of::<V::T>();
+        if size == 0 {
+            return None;
+        }
+        let offset = (len * size).checked_sub(mem::size_of::<Self>())?;
+        let misaligned = (buf.as_ptr().addr() + offset) % mem::align_of::<Self>();
+        let offset = offset.checked_sub(misaligned)?;
+        let shared = unsafe { buf.cast::<u8>().add(offset).cast::<Self>() };
+        Some((offset / size, shared))
+    }
+
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            2 * self.cap - (head - tail)
+        }
+    }
+
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        self.distance(head, tail)
+    }
+
+    fn next(&self, index: usize) -> usize {
+        if index + 1 == 2 * self.cap { 0 } else { index + 1 }
+    }
+
+    fn slot(&self, counter: usize) -> *mut V::T {
+        unsafe { self.buf.as_ptr().add(self.index(counter)) }
+    }
+
+    fn index(&self, counter: usize) -> usize {
+        if counter >= self.cap { counter - self.cap } else { counter }
+    }
+
+    /// Release a handle, the last one appends the remaining elements to vec
+    ///
+    /// # Safety
+    /// - `this` is not used after released
+    unsafe fn release(this: NonNull<Self>) {
+        let handles = unsafe { &this.as_ref().handles };
+        if handles.fetch_sub(1, Ordering::AcqRel) != 1 {
+            return;
+        }
+
+        let this = unsafe { ptr::read(this.as_ptr()) };
+        let head = this.head.load(Ordering::Relaxed);
+        let len = this.distance(head, this.tail.load(Ordering::Relaxed));
+        if len == 0 {
+            return;
+        }
+
+        unsafe {
+            let buf = this.buf.as_ptr().cast::<MaybeUninit<V::T>>();
+            slice::from_raw_parts_mut(buf, this.cap).rotate_left(this.index(head));
+        }
+        let new_len = this.vec_len + len;
+        let mut vec = this.vec;
+        unsafe {
+            match this.len_ptr {
+                Some(len_ptr) => len_ptr.write(new_len),
+                None => vec.as_mut().set_len(new_len),
+            }
+        }
+    }
+}
+
+/// The pushing half of [`split_spsc`]
+///
+/// [`split_spsc`]: SplitSpsc::split_spsc
+pub struct Producer<'a, T, V: VecLike<T = T>> {
+    shared: NonNull<Shared<V>>,
+    _phantom: PhantomData<&'a mut V>,
+}
+impl<T, V: VecLike<T = T>> Producer<'_, T, V> {
+    fn shared(&self) -> &Shared<V> {
+        unsafe { self.shared.as_ref() }
+    }
+
+    /// Push a value to the back of ring
+    ///
+    /// # Errors
+    /// - [`Err`] when the ring is full
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let shared = self.shared();
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let head = shared.head.load(Ordering::Acquire);
+        if shared.distance(head, tail) == shared.cap {
+            return Err(value);
+        }
+
+        unsafe { shared.slot(tail).write(value) }
+        shared.tail.store(shared.next(tail), Ordering::Release);
+        Ok(())
+    }
+
+    /// Count of elements in the ring, the [`Consumer`] may pop concurrently
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shared().len()
+    }
+
+    /// Returns `true` if the ring is empty, the [`Consumer`] may pop concurrently
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the ring is full, the [`Consumer`] may pop concurrently
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Capacity of the ring
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shared().cap
+    }
+}
+impl<T, V: VecLike<T = T>> Drop for Producer<'_, T, V> {
+    fn drop(&mut self) {
+        unsafe { Shared::release(self.shared) }
+    }
+}
+impl<T, V: VecLike<T = T>> Debug for Producer<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + Send> Send for Producer<'_, T, V> {
+}
+unsafe impl<T: Send, V: VecLike<T = T> + Send> Sync for Producer<'_, T, V> {
+}
+
+/// The popping half of [`split_spsc`]
+///
+/// [`split_spsc`]: SplitSpsc::split_spsc
+pub struct Consumer<'a, T, V: VecLike<T = T>> {
+    shared: NonNull<Shared<V>>,
+    _phantom: PhantomData<&'a mut V>,
+}
+impl<T, V: VecLike<T = T>> Consumer<'_, T, V> {
+    fn shared(&self) -> &Shared<V> {
+        unsafe { self.shared.as_ref() }
+    }
+
+    /// Pop a value from the front of ring
+    ///
+    /// # Results
+    /// - [`None`] when the ring is empty
+    pub fn pop(&mut self) -> Option<T> {
+        let shared = self.shared();
+        let head = shared.head.load(Ordering::Relaxed);
+        let tail = shared.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        let value = unsafe { ptr::read(shared.slot(head)) };
+        shared.head.store(shared.next(head), Ordering::Release);
+        Some(value)
+    }
+
+    /// Get the front value of ring
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        let shared = self.shared();
+        let head = shared.head.load(Ordering::Relaxed);
+        let tail = shared.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        Some(unsafe { &*shared.slot(head) })
+    }
+
+    /// Count of elements in the ring, the [`Producer`] may push concurrently
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shared().len()
+    }
+
+    /// Returns `true` if the ring is empty, the [`Producer`] may push concurrently
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Capacity of the ring
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.shared().cap
+    }
+}
+impl<T, V: VecLike<T = T>> Drop for Consumer<'_, T, V> {
+    fn drop(&mut self) {
+        unsafe { Shared::release(self.shared) }
+    }
+}
+impl<T, V: VecLike<T = T>> Debug for Consumer<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + Send> Send for Consumer<'_, T, V> {
+}
+unsafe impl<T: Send + Sync, V: VecLike<T = T> + Send> Sync for Consumer<'_, T, V> {
+}
@@ -0,0 +1,579 @@
+//! Fixed-capacity [`VecLike`] storages, no allocator is required
+
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+    slice,
+};
+
+use crate::{
+    tailvec::{slice_assume_init, slice_assume_init_mut},
+    VecLike,
+};
+
+/// Inline array backed [`VecLike`], capacity is `N`
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = ArrayVecStorage::<i32, 4>::new();
+/// vec.push(1).unwrap();
+/// vec.push(2).unwrap();
+///
+/// let (left, mut rest) = vec.split_tail(1);
+/// assert_eq!(left, &mut [1]);
+/// assert_eq!(rest.capacity(), 3);
+/// rest.push(3).unwrap();
+/// rest.push(4).unwrap();
+/// assert_eq!(rest.push(5), Err(5));
+///
+/// drop(rest);
+/// assert_eq!(vec, [1, 2, 3, 4]);
+/// ```
+pub struct ArrayVecStorage<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    len: usize,
+}
+impl<T, const N: usize> ArrayVecStorage<T, N> {
+    /// Create empty storage
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Push a value, like [`TailVec::push`]
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal `N`
+    ///
+    /// [`TailVec::push`]: crate::TailVec::push
+    /// [`len()`]: VecLike::len
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let Some(slot) = self.data.get_mut(self.len) else {
+            return Err(value);
+        };
+        slot.write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Pop last value
+    pub fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        Some(unsafe { self.data[self.len].assume_init_read() })
+    }
+
+    /// Get initialized slice
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice_assume_init(&self.data[..self.len]) }
+    }
+
+    /// Get initialized mutable slice
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        unsafe { slice_assume_init_mut(&mut self.data[..self.len]) }
+    }
+}
+impl<T, const N: usize> Default for ArrayVecStorage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T, const N: usize> Drop for ArrayVecStorage<T, N> {
+    fn drop(&mut self) {
+        let elements: *mut [T] = self.as_slice_mut();
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elements) }
+    }
+}
+impl<T, const N: usize> Deref for ArrayVecStorage<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T, const N: usize> DerefMut for ArrayVecStorage<T, N> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_slice_mut()
+    }
+}
+impl<T: Debug, const N: usize> Debug for ArrayVecStorage<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+impl<T: PartialEq<U>, U, const N: usize, const M: usize> PartialEq<[U; M]> for ArrayVecStorage<T, N> {
+    fn eq(&self, other: &[U; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+unsafe impl<T, const N: usize> VecLike for ArrayVecStorage<T, N> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        &mut self.data[self.len..]
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        let Self { data, len } = self;
+        (&mut data[*len..], Some(NonNull::from(len)))
+    }
+}
+
+/// Borrowed uninitialized slice backed [`VecLike`]
+///
+/// The initialized elements are dropped when [`SliceStorage`] is dropped
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// use core::mem::MaybeUninit;
+///
+/// let mut buf = [const { MaybeUninit::uninit() }; 4];
+/// let mut vec = SliceStorage::new(&mut buf);
+///
+/// let (_, mut rest) = vec.split_tail(0);
+/// rest.push(1).unwrap();
+/// rest.push(2).unwrap();
+/// drop(rest);
+///
+/// assert_eq!(vec.as_slice(), &[1, 2]);
+/// assert_eq!(vec.capacity(), 4);
+/// ```
+pub struct SliceStorage<'a, T> {
+    buf: &'a mut [MaybeUninit<T>],
+    len: usize,
+}
+impl<'a, T> SliceStorage<'a, T> {
+    /// Create empty storage from uninitialized buffer
+    pub fn new(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// Create storage from buffer, the first `len` elements is initialized
+    ///
+    /// # Safety
+    /// - `len` must be less than or equal `buf.len()`
+    /// - `buf[..len]` must be initialized
+    /// - The ownership of `buf[..len]` is transferred to [`SliceStorage`]
+    pub unsafe fn from_parts(buf: &'a mut [MaybeUninit<T>], len: usize) -> Self {
+        debug_assert!(len <= buf.len());
+        Self { buf, len }
+    }
+
+    /// Get initialized slice
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice_assume_init(&self.buf[..self.len]) }
+    }
+
+    /// Get initialized mutable slice
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        unsafe { slice_assume_init_mut(&mut self.buf[..self.len]) }
+    }
+
+    /// Consume into initialized mutable slice, elements will not be dropped
+    #[must_use]
+    pub fn into_slice(self) -> &'a mut [T] {
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len;
+        let buf = mem::take(&mut this.buf);
+        unsafe { slice_assume_init_mut(&mut buf[..len]) }
+    }
+}
+impl<T> Drop for SliceStorage<'_, T> {
+    fn drop(&mut self) {
+        let elements: *mut [T] = self.as_slice_mut();
+        self.len = 0;
+        unsafe { ptr::drop_in_place(elements) }
+    }
+}
+impl<T> Deref for SliceStorage<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T> DerefMut for SliceStorage<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_slice_mut()
+    }
+}
+impl<T: Debug> Debug for SliceStorage<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+unsafe impl<T> VecLike for SliceStorage<'_, T> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        &mut self.buf[self.len..]
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        let Self { buf, len } = self;
+        (&mut buf[*len..], Some(NonNull::from(len)))
+    }
+}
+
+/// Foreign `(ptr, len, cap)` buffer backed [`VecLike`], for FFI
+///
+/// The length is read from and written through `len` directly,
+/// the elements are not dropped when [`RawStorage`] is dropped,
+/// the buffer is still owned by foreign code.
+///
+/// # Examples
+///
+/// A C library that appends into caller-provided capacity:
+///
+/// ```c
+/// typedef int (*append_cb)(void *ctx, int value);
+///
+/// /* buf[0..*len] is initialized, capacity is cap */
+/// void fill_numbers(int *buf, size_t *len, size_t cap);
+/// void produce(append_cb cb, void *ctx);
+/// ```
+///
+/// ```
+/// # use tailvec::*;
+/// use core::ffi::{c_int, c_void};
+///
+/// extern "C" fn append(ctx: *mut c_void, value: c_int) -> c_int {
+///     let tail = unsafe { &mut *ctx.cast::<TailVec<'_, c_int, RawStorage<'_, c_int>>>() };
+///     tail.push(value).map_or(-1, |()| 0)
+/// }
+///
+/// // foreign buffer
+/// let mut buf: [c_int; 4] = [1, 2, 0, 0];
+/// let mut len = 2;
+///
+/// let mut storage = unsafe {
+///     RawStorage::from_raw_parts(buf.as_mut_ptr(), &mut len, buf.len())
+/// };
+/// let (_, mut rest) = storage.split_tail(2);
+/// let ctx = (&mut rest as *mut TailVec<'_, _, _>).cast::<c_void>();
+/// assert_eq!(append(ctx, 3), 0);
+/// assert_eq!(append(ctx, 4), 0);
+/// assert_eq!(append(ctx, 5), -1);
+/// drop(rest);
+/// drop(storage);
+///
+/// assert_eq!(len, 4);
+/// assert_eq!(buf, [1, 2, 3, 4]);
+/// ```
+pub struct RawStorage<'a, T> {
+    ptr: NonNull<T>,
+    len: &'a mut usize,
+    cap: usize,
+    _phantom: PhantomData<&'a mut [T]>,
+}
+impl<'a, T> RawStorage<'a, T> {
+    /// Create storage from foreign buffer
+    ///
+    /// # Safety
+    /// - `ptr` must be valid for reads and writes of `cap` elements,
+    ///   and properly aligned, it can be null only when `cap` is zero
+    /// - `*len` must be less than or equal `cap`
+    /// - the first `*len` elements must be initialized
+    /// - the buffer must not be accessed by others for `'a`
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: &'a mut usize, cap: usize) -> Self {
+        debug_assert!(*len <= cap);
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| {
+            debug_assert_eq!(cap, 0, "null pointer with non-zero capacity");
+            NonNull::dangling()
+        });
+        Self { ptr, len, cap, _phantom: PhantomData }
+    }
+
+    /// Get initialized slice
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), *self.len) }
+    }
+
+    /// Get initialized mutable slice
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), *self.len) }
+    }
+}
+impl<T> Deref for RawStorage<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T> DerefMut for RawStorage<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_slice_mut()
+    }
+}
+impl<T: Debug> Debug for RawStorage<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+unsafe impl<T: Send> Send for RawStorage<'_, T> {
+}
+unsafe impl<T: Sync> Sync for RawStorage<'_, T> {
+}
+unsafe impl<T> VecLike for RawStorage<'_, T> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        *self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        unsafe {
+            let ptr = self.ptr.as_ptr().add(*self.len);
+            slice::from_raw_parts_mut(ptr.cast(), self.cap - *self.len)
+        }
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        *self.len = new_len;
+    }
+
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        let spare = unsafe {
+            let ptr = self.ptr.as_ptr().add(*self.len);
+            slice::from_raw_parts_mut(ptr.cast(), self.cap - *self.len)
+        };
+        (spare, Some(NonNull::from(&mut *self.len)))
+    }
+}
+
+/// Vectors whose slots may be inside themselves, see [`InlineStorage`]
+///
+/// # Safety
+/// - [`as_ptr`] and [`as_mut_ptr`] must be valid for [`capacity`] elements,
+///   until `Self` is moved or accessed by others
+/// - the first [`len`] elements must be initialized,
+///   and the others can be uninitialized
+/// - [`set_len`] must be like [`VecLike::set_len`]
+///
+/// [`as_ptr`]: InlineVec::as_ptr
+/// [`as_mut_ptr`]: InlineVec::as_mut_ptr
+/// [`capacity`]: InlineVec::capacity
+/// [`len`]: InlineVec::len
+/// [`set_len`]: InlineVec::set_len
+pub unsafe trait InlineVec {
+    /// Element type
+    type T;
+
+    /// Initialized elements count
+    fn len(&self) -> usize;
+
+    /// Allocated slots count
+    fn capacity(&self) -> usize;
+
+    /// Pointer of the first slot
+    fn as_ptr(&self) -> *const Self::T;
+
+    /// Mutable pointer of the first slot
+    fn as_mut_ptr(&mut self) -> *mut Self::T;
+
+    /// Set initialized elements count
+    ///
+    /// # Safety
+    /// - Like the [`VecLike::set_len`]
+    unsafe fn set_len(&mut self, new_len: usize);
+
+    /// Return collection is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+unsafe impl<V: InlineVec + ?Sized> InlineVec for &mut V {
+    type T = V::T;
+
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn capacity(&self) -> usize {
+        (**self).capacity()
+    }
+
+    fn as_ptr(&self) -> *const Self::T {
+        (**self).as_ptr()
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut Self::T {
+        (**self).as_mut_ptr()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            (**self).set_len(new_len);
+        }
+    }
+}
+
+/// [`InlineVec`] backed [`VecLike`], its length is kept outside the vector
+///
+/// The length fields of third-party inline vectors are private,
+/// [`TailVec`] cannot write them without a `&mut` over the slots,
+/// which invalidates the head slice of [`split_tail`].
+/// [`InlineStorage`] writes the length back to the vector
+/// when it is dropped or [`into_inner`].
+///
+/// # Leaking
+/// If the [`InlineStorage`] goes out of scope without being dropped
+/// (due to [`mem::forget`], for example),
+/// the elements of the vector are leaked.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "arrayvec")] {
+/// # use tailvec::*;
+/// let mut vec = arrayvec::ArrayVec::<i32, 4>::new();
+/// vec.push(1);
+///
+/// let mut storage = InlineStorage::new(&mut vec);
+/// let (left, mut rest) = storage.split_tail(1);
+/// rest.push(2).unwrap();
+/// drop(rest);
+/// left[0] = 3;
+/// drop(storage);
+///
+/// assert_eq!(vec.as_slice(), [3, 2]);
+/// # }
+/// ```
+///
+/// [`TailVec`]: crate::TailVec
+/// [`split_tail`]: crate::SplitTail::split_tail
+/// [`into_inner`]: InlineStorage::into_inner
+/// [`mem::forget`]: core::mem::forget
+pub struct InlineStorage<V: InlineVec> {
+    vec: V,
+    len: usize,
+}
+impl<V: InlineVec> InlineStorage<V> {
+    /// Create storage from vector, the elements are kept
+    pub fn new(mut vec: V) -> Self {
+        let len = vec.len();
+        unsafe { vec.set_len(0) }
+        Self { vec, len }
+    }
+
+    /// Write the length back, and into the vector
+    #[must_use]
+    pub fn into_inner(self) -> V {
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len;
+        unsafe {
+            this.vec.set_len(len);
+            ptr::read(&this.vec)
+        }
+    }
+
+    /// Get initialized slice
+    #[must_use]
+    pub fn as_slice(&self) -> &[V::T] {
+        unsafe { slice::from_raw_parts(self.vec.as_ptr(), self.len) }
+    }
+
+    /// Get initialized mutable slice
+    pub fn as_slice_mut(&mut self) -> &mut [V::T] {
+        unsafe { slice::from_raw_parts_mut(self.vec.as_mut_ptr(), self.len) }
+    }
+}
+impl<V: InlineVec> Drop for InlineStorage<V> {
+    fn drop(&mut self) {
+        unsafe { self.vec.set_len(self.len) }
+    }
+}
+impl<V: InlineVec> Deref for InlineStorage<V> {
+    type Target = [V::T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<V: InlineVec> DerefMut for InlineStorage<V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_slice_mut()
+    }
+}
+impl<V: InlineVec> Debug for InlineStorage<V>
+where V::T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+unsafe impl<V: InlineVec> VecLike for InlineStorage<V> {
+    type T = V::T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        self.spare_capacity_and_len_mut().0
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        self.len = new_len;
+    }
+
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        let Self { vec, len } = self;
+        let spare = unsafe {
+            let ptr = vec.as_mut_ptr().add(*len);
+            slice::from_raw_parts_mut(ptr.cast(), vec.capacity() - *len)
+        };
+        (spare, Some(NonNull::from(len)))
+    }
+}
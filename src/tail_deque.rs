@@ -0,0 +1,689 @@
+use core::{
+    fmt::{self, Debug},
+    iter::Chain,
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{Index, IndexMut, RangeBounds},
+    ptr,
+    slice,
+};
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+extern crate alloc;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+use crate::{Drain, TailVec, VecLike};
+
+/// Double-ended queue over [`TailVec`], create from [`into_deque`]
+///
+/// It is a ring buffer in the [`TailVec`] capacity,
+/// [`push_front`], [`push_back`], [`pop_front`] and [`pop_back`] are `O(1)`,
+/// the elements may wrap around the end of capacity.
+///
+/// The elements are made contiguous in `O(n)` by [`as_slice`], [`as_slice_mut`]
+/// and [`drain`] etc, they slide to the split point when [`TailDeque`] is dropped,
+/// or [`into_tail`].
+///
+/// # Leaking
+/// If the [`TailDeque`] goes out of scope without being dropped
+/// (due to [`mem::forget`], for example),
+/// the elements of the deque are leaked.
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// let mut vec = vec![0];
+/// vec.reserve_exact(4);
+/// let (_, rest) = vec.split_tail(1);
+///
+/// let mut deque = rest.into_deque();
+/// deque.push_back(2).unwrap();
+/// deque.push_front(1).unwrap();
+/// deque.push_back(3).unwrap();
+/// assert_eq!(deque, [1, 2, 3]);
+/// assert_eq!(deque.pop_front(), Some(1));
+/// assert_eq!(deque.pop_front(), Some(2));
+/// drop(deque);
+///
+/// assert_eq!(vec, [0, 3]);
+/// ```
+///
+/// [`into_deque`]: TailVec::into_deque
+/// [`push_front`]: TailDeque::push_front
+/// [`push_back`]: TailDeque::push_back
+/// [`pop_front`]: TailDeque::pop_front
+/// [`pop_back`]: TailDeque::pop_back
+/// [`as_slice`]: TailDeque::as_slice
+/// [`as_slice_mut`]: TailDeque::as_slice_mut
+/// [`drain`]: TailDeque::drain
+/// [`into_tail`]: TailDeque::into_tail
+/// [`mem::forget`]: core::mem::forget
+pub struct TailDeque<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
+    /// Its length is the deque length,
+    /// the elements start at `head` rather than zero, and may wrap around
+    tail: TailVec<'a, T, V>,
+    head: usize,
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
+    /// Into double-ended queue, the elements are kept
+    ///
+    /// *See [`TailDeque`] for more documents*
+    #[must_use]
+    pub fn into_deque(self) -> TailDeque<'a, T, V> {
+        TailDeque { tail: self, head: 0 }
+    }
+}
+
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailDeque<'a, T, V> {
+    /// Slide the elements to the split point, and into [`TailVec`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 2, 3];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// assert_eq!(deque.pop_front(), Some(1));
+    ///
+    /// let mut rest = deque.into_tail();
+    /// assert_eq!(rest, [2, 3]);
+    /// rest.push(4).unwrap();
+    /// assert_eq!(rest, [2, 3, 4]);
+    /// ```
+    #[must_use]
+    pub fn into_tail(self) -> TailVec<'a, T, V> {
+        let mut this = ManuallyDrop::new(self);
+        this.make_contiguous();
+        unsafe { ptr::read(&this.tail) }
+    }
+
+    /// Move the elements to the split point
+    fn make_contiguous(&mut self) {
+        let (head, len, cap) = (self.head, self.len(), self.capacity());
+        if head == 0 {
+            return;
+        }
+        let ptr = self.tail.as_mut_ptr();
+        unsafe {
+            if head + len <= cap {
+                ptr::copy(ptr.add(head), ptr, len);
+            } else {
+                slice::from_raw_parts_mut(ptr.cast::<MaybeUninit<T>>(), cap).rotate_left(head);
+            }
+        }
+        self.head = 0;
+    }
+
+    /// Index of capacity of the `index`-th element
+    fn physical(&self, index: usize) -> usize {
+        let index = self.head + index;
+        if index >= self.capacity() { index - self.capacity() } else { index }
+    }
+
+    fn slot(&mut self, index: usize) -> *mut T {
+        let index = self.physical(index);
+        unsafe { self.tail.as_mut_ptr().add(index) }
+    }
+
+    /// Elements count of the deque
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tail.len()
+    }
+
+    /// Returns `true` if the deque contains no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Capacity of the deque, equal [`TailVec::capacity`]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.tail.capacity()
+    }
+
+    /// Get the front part and the wrapped back part of elements
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(3);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// deque.extend([2, 3]);
+    /// deque.push_front(1).unwrap();
+    /// assert_eq!(deque.as_slices(), (&[1][..], &[2, 3][..]));
+    /// ```
+    #[must_use]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, len, cap) = (self.head, self.len(), self.capacity());
+        let first = len.min(cap - head);
+        let ptr = self.tail.as_ptr();
+        unsafe {
+            (
+                slice::from_raw_parts(ptr.add(head), first),
+                slice::from_raw_parts(ptr, len - first),
+            )
+        }
+    }
+
+    /// Get the front part and the wrapped back part of elements
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, len, cap) = (self.head, self.len(), self.capacity());
+        let first = len.min(cap - head);
+        let ptr = self.tail.as_mut_ptr();
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr.add(head), first),
+                slice::from_raw_parts_mut(ptr, len - first),
+            )
+        }
+    }
+
+    /// Make the elements contiguous, and get initialized slice
+    pub fn as_slice(&mut self) -> &[T] {
+        self.as_slice_mut()
+    }
+
+    /// Make the elements contiguous, and get initialized mutable slice
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(3);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// deque.extend([2, 3]);
+    /// deque.push_front(1).unwrap();
+    /// assert_eq!(deque.as_slice_mut(), [1, 2, 3]);
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        self.make_contiguous();
+        self.tail.as_slice_mut()
+    }
+
+    /// Iterate the elements from front to back
+    pub fn iter(&self) -> Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+
+    /// Iterate the mutable elements from front to back
+    pub fn iter_mut(&mut self) -> Chain<slice::IterMut<'_, T>, slice::IterMut<'_, T>> {
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back)
+    }
+
+    /// Get the `index`-th element from front
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let (front, back) = self.as_slices();
+        front.get(index).or_else(|| back.get(index - front.len()))
+    }
+
+    /// Get the `index`-th mutable element from front
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let (front, back) = self.as_mut_slices();
+        let front_len = front.len();
+        match front.get_mut(index) {
+            Some(ele) => Some(ele),
+            None => back.get_mut(index - front_len),
+        }
+    }
+
+    /// Push a value to the back
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailDeque::len
+    /// [`capacity()`]: TailDeque::capacity
+    pub fn push_back(&mut self, value: T) -> Result<(), T> {
+        let len = self.len();
+        if len == self.capacity() {
+            return Err(value);
+        }
+        unsafe {
+            self.slot(len).write(value);
+            self.tail.set_len(len + 1);
+        }
+        Ok(())
+    }
+
+    /// Push a value to the front
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// [`len()`]: TailDeque::len
+    /// [`capacity()`]: TailDeque::capacity
+    pub fn push_front(&mut self, value: T) -> Result<(), T> {
+        let len = self.len();
+        if len == self.capacity() {
+            return Err(value);
+        }
+        self.head = self.physical(self.capacity() - 1);
+        unsafe {
+            self.slot(0).write(value);
+            self.tail.set_len(len + 1);
+        }
+        Ok(())
+    }
+
+    /// Pop the back value
+    ///
+    /// # Results
+    /// - [`None`] when [`len()`] by zero
+    ///
+    /// [`len()`]: TailDeque::len
+    pub fn pop_back(&mut self) -> Option<T> {
+        let new_len = self.len().checked_sub(1)?;
+        unsafe {
+            self.tail.set_len(new_len);
+            Some(self.slot(new_len).read())
+        }
+    }
+
+    /// Pop the front value
+    ///
+    /// # Results
+    /// - [`None`] when [`len()`] by zero
+    ///
+    /// [`len()`]: TailDeque::len
+    pub fn pop_front(&mut self) -> Option<T> {
+        let new_len = self.len().checked_sub(1)?;
+        unsafe {
+            let value = self.slot(0).read();
+            self.head = self.physical(1);
+            self.tail.set_len(new_len);
+            Some(value)
+        }
+    }
+
+    /// Insert an element at `index`, the shorter side is shifted
+    ///
+    /// # Panics
+    /// - `index` greater than [`len()`]
+    ///
+    /// # Errors
+    /// - [`Err`] when [`len()`] equal [`capacity()`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(4);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// deque.extend([1, 4]);
+    ///
+    /// assert_eq!(deque.insert(1, 2), Ok(()));
+    /// assert_eq!(deque.insert(2, 3), Ok(()));
+    /// assert_eq!(deque, [1, 2, 3, 4]);
+    /// assert_eq!(deque.insert(0, 0), Err(0)); // Overflow of capacity
+    /// ```
+    ///
+    /// [`len()`]: TailDeque::len
+    /// [`capacity()`]: TailDeque::capacity
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, element: T) -> Result<(), T> {
+        let len = self.len();
+        assert!(index <= len, "insertion index (is {index}) should be <= len (is {len})");
+        if len == self.capacity() {
+            return Err(element);
+        }
+
+        unsafe {
+            if index < len / 2 {
+                self.head = self.physical(self.capacity() - 1);
+                for i in 0..index {
+                    ptr::copy_nonoverlapping(self.slot(i + 1), self.slot(i), 1);
+                }
+            } else {
+                for i in (index..len).rev() {
+                    ptr::copy_nonoverlapping(self.slot(i), self.slot(i + 1), 1);
+                }
+            }
+            self.slot(index).write(element);
+            self.tail.set_len(len + 1);
+        }
+        Ok(())
+    }
+
+    /// Remove and return the element at `index`, the shorter side is shifted
+    ///
+    /// # Panics
+    /// - `index` greater than or equal [`len()`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    ///
+    /// assert_eq!(deque.remove(1), 2);
+    /// assert_eq!(deque.remove(2), 4);
+    /// assert_eq!(deque, [1, 3]);
+    /// ```
+    ///
+    /// [`len()`]: TailDeque::len
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(index < len, "removal index (is {index}) should be < len (is {len})");
+
+        unsafe {
+            let value = self.slot(index).read();
+            if index < len / 2 {
+                for i in (0..index).rev() {
+                    ptr::copy_nonoverlapping(self.slot(i), self.slot(i + 1), 1);
+                }
+                self.head = self.physical(1);
+            } else {
+                for i in index + 1..len {
+                    ptr::copy_nonoverlapping(self.slot(i), self.slot(i - 1), 1);
+                }
+            }
+            self.tail.set_len(len - 1);
+            value
+        }
+    }
+
+    /// Retains only the elements specified by the predicate,
+    /// the elements are made contiguous first
+    ///
+    /// *See [`TailVec::retain`] for more documents*
+    pub fn retain<F>(&mut self, f: F)
+    where F: FnMut(&T) -> bool,
+    {
+        self.make_contiguous();
+        self.tail.retain(f);
+    }
+
+    /// Retains only the elements specified by the predicate,
+    /// the elements are made contiguous first
+    ///
+    /// *See [`TailVec::retain_mut`] for more documents*
+    pub fn retain_mut<F>(&mut self, f: F)
+    where F: FnMut(&mut T) -> bool,
+    {
+        self.make_contiguous();
+        self.tail.retain_mut(f);
+    }
+
+    /// Removes the specified range in bulk, returning all removed elements,
+    /// the elements are made contiguous first
+    ///
+    /// *See [`TailVec::drain`] for more documents*
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![2, 3, 4];
+    /// vec.reserve_exact(1);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// assert_eq!(deque.drain(1..3).collect::<Vec<_>>(), [2, 3]);
+    /// assert_eq!(deque, [1, 4]);
+    /// ```
+    #[track_caller]
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, V>
+    where R: RangeBounds<usize>,
+    {
+        self.make_contiguous();
+        self.tail.drain(range)
+    }
+
+    /// Remove and return the element at `index`,
+    /// the last element is moved to `index`
+    ///
+    /// # Results
+    /// - [`None`] when `index` greater than or equal [`len()`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    ///
+    /// assert_eq!(deque.swap_remove_back(1), Some(2));
+    /// assert_eq!(deque, [1, 4, 3]);
+    /// assert_eq!(deque.swap_remove_back(3), None);
+    /// ```
+    ///
+    /// [`len()`]: TailDeque::len
+    pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            let value = self.slot(index).read();
+            if index != len - 1 {
+                ptr::copy_nonoverlapping(self.slot(len - 1), self.slot(index), 1);
+            }
+            self.tail.set_len(len - 1);
+            Some(value)
+        }
+    }
+
+    /// Remove and return the element at `index`,
+    /// the first element is moved to `index`
+    ///
+    /// # Results
+    /// - [`None`] when `index` greater than or equal [`len()`]
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    ///
+    /// assert_eq!(deque.swap_remove_front(2), Some(3));
+    /// assert_eq!(deque, [2, 1, 4]);
+    /// assert_eq!(deque.swap_remove_front(3), None);
+    /// ```
+    ///
+    /// [`len()`]: TailDeque::len
+    pub fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            let value = self.slot(index).read();
+            if index != 0 {
+                ptr::copy_nonoverlapping(self.slot(0), self.slot(index), 1);
+            }
+            self.head = self.physical(1);
+            self.tail.set_len(len - 1);
+            Some(value)
+        }
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping the rest
+    ///
+    /// No truncating when `len` greater [`len()`]
+    ///
+    /// [`len()`]: TailDeque::len
+    pub fn truncate(&mut self, len: usize) {
+        for _ in len..self.len() {
+            self.pop_back();
+        }
+    }
+
+    /// Resizes, until [`len()`] equal to `new_len`,
+    /// extend the back using the [`Clone::clone`] of `value`
+    ///
+    /// # Errors
+    /// - [`Err`] when `new_len` greater than [`capacity()`],
+    ///   then the deque is not changed.
+    ///
+    /// [`len()`]: TailDeque::len
+    /// [`capacity()`]: TailDeque::capacity
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<(), T>
+    where T: Clone,
+    {
+        if new_len > self.capacity() {
+            return Err(value);
+        }
+        if new_len <= self.len() {
+            self.truncate(new_len);
+        } else {
+            for _ in self.len() + 1..new_len {
+                let res = self.push_back(value.clone());
+                debug_assert!(res.is_ok());
+            }
+            let res = self.push_back(value);
+            debug_assert!(res.is_ok());
+        }
+        Ok(())
+    }
+
+    /// Resizes, until [`len()`] equal to `new_len`,
+    /// extend the back using the results of `f`
+    ///
+    /// # Errors
+    /// - [`Err`] when `new_len` greater than [`capacity()`],
+    ///   then the deque is not changed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use tailvec::*;
+    /// let mut vec = Vec::with_capacity(4);
+    /// let (_, rest) = vec.split_tail(0);
+    /// let mut deque = rest.into_deque();
+    /// deque.push_front(1).unwrap();
+    ///
+    /// assert!(deque.resize_with(5, || 0).is_err());
+    /// assert!(deque.resize_with(3, || 0).is_ok());
+    /// assert_eq!(deque, [1, 0, 0]);
+    /// deque.truncate(1);
+    /// assert_eq!(deque, [1]);
+    /// ```
+    ///
+    /// [`len()`]: TailDeque::len
+    /// [`capacity()`]: TailDeque::capacity
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F) -> Result<(), F>
+    where F: FnMut() -> T,
+    {
+        if new_len > self.capacity() {
+            return Err(f);
+        }
+        if new_len <= self.len() {
+            self.truncate(new_len);
+        } else {
+            for _ in self.len()..new_len {
+                let res = self.push_back(f());
+                debug_assert!(res.is_ok());
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove and drop all elements
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back): (*mut [T], *mut [T]) = (front, back);
+        self.head = 0;
+        unsafe {
+            self.tail.set_len(0);
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Drop for TailDeque<'_, T, V> {
+    fn drop(&mut self) {
+        self.make_contiguous();
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Index<usize> for TailDeque<'_, T, V> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("Out of bounds access")
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> IndexMut<usize> for TailDeque<'_, T, V> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("Out of bounds access")
+    }
+}
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for TailDeque<'_, T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T, U, V, const N: usize> PartialEq<[U; N]> for TailDeque<'_, T, V>
+where T: PartialEq<U>,
+      V: VecLike<T = T> + ?Sized,
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        *self == other[..]
+    }
+}
+impl<T, U, V> PartialEq<[U]> for TailDeque<'_, T, V>
+where T: PartialEq<U>,
+      V: VecLike<T = T> + ?Sized,
+{
+    fn eq(&self, other: &[U]) -> bool {
+        let (front, back) = self.as_slices();
+        self.len() == other.len()
+            && *front == other[..front.len()]
+            && *back == other[front.len()..]
+    }
+}
+impl<T, V: VecLike<T = T> + ?Sized> Extend<T> for TailDeque<'_, T, V> {
+    /// Push all elements to the back
+    ///
+    /// # Panics
+    /// [`iter.count()`] greater than `capacity() - len()`
+    ///
+    /// [`iter.count()`]: Iterator::count
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|ele| {
+                assert!(self.push_back(ele).is_ok(),
+                        "Overflow of capacity when extend elements");
+            });
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r TailDeque<'_, T, V> {
+    type Item = &'r T;
+    type IntoIter = Chain<slice::Iter<'r, T>, slice::Iter<'r, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'r, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'r mut TailDeque<'_, T, V> {
+    type Item = &'r mut T;
+    type IntoIter = Chain<slice::IterMut<'r, T>, slice::IterMut<'r, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+unsafe impl<T: Send, V: VecLike<T = T> + ?Sized + Send> Send for TailDeque<'_, T, V> {
+}
+unsafe impl<T: Sync, V: VecLike<T = T> + ?Sized + Sync> Sync for TailDeque<'_, T, V> {
+}
@@ -6,25 +6,30 @@ use core::{
     fmt::{self, Debug},
     hash::{Hash, Hasher},
     marker::PhantomData,
-    mem::{transmute, MaybeUninit},
+    mem::{transmute, ManuallyDrop, MaybeUninit},
+    cell::RefMut,
     ops::{Deref, DerefMut, Index, IndexMut},
     panic::{RefUnwindSafe, UnwindSafe},
     ptr::{self, NonNull},
 };
-#[cfg(not(feature = "std"))]
+#[cfg(all(feature = "alloc", not(feature = "std")))]
 extern crate alloc;
-#[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(all(feature = "allocator_api", not(feature = "std")))]
+use alloc::alloc::Allocator;
+#[cfg(all(feature = "allocator_api", feature = "std"))]
+use std::alloc::Allocator;
 
 #[allow(clippy::transmute_ptr_to_ptr)]
-unsafe fn slice_assume_init<T>(
+pub(crate) unsafe fn slice_assume_init<T>(
     slice: &[MaybeUninit<T>],
 ) -> &[T] {
     unsafe { transmute(slice) }
 }
 
 #[allow(clippy::transmute_ptr_to_ptr)]
-unsafe fn slice_assume_init_mut<T>(
+pub(crate) unsafe fn slice_assume_init_mut<T>(
     slice: &mut [MaybeUninit<T>],
 ) -> &mut [T] {
     unsafe { transmute(slice) }
@@ -33,6 +38,28 @@ unsafe fn slice_assume_init_mut<T>(
 
 /// Like vec struct trait
 ///
+/// It is forwarded by `&mut V`, [`Box<V>`], [`RefMut<V>`], [`MutexGuard<V>`]
+/// and [`RwLockWriteGuard<V>`]
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// use std::cell::RefCell;
+///
+/// fn push_tail<V: VecLike<T = i32>>(vec: V) -> V {
+///     let mut vec = vec;
+///     let (_, mut rest) = vec.split_tail(vec.len());
+///     rest.push(3).unwrap();
+///     drop(rest);
+///     vec
+/// }
+///
+/// let cell = RefCell::new(Vec::with_capacity(3));
+/// push_tail(cell.borrow_mut());
+/// push_tail(&mut *cell.borrow_mut());
+/// assert_eq!(*cell.borrow(), [3, 3]);
+/// ```
+///
 /// # Safety
 /// - [`self.capacity()`] must be equals [`self.len()`]
 ///   plus [`self.spare_capacity_mut().len()`]
@@ -40,6 +67,8 @@ unsafe fn slice_assume_init_mut<T>(
 /// [`self.len()`]: VecLike::len
 /// [`self.capacity()`]: VecLike::capacity
 /// [`self.spare_capacity_mut().len()`]: VecLike::spare_capacity_mut
+/// [`MutexGuard<V>`]: https://doc.rust-lang.org/std/sync/struct.MutexGuard.html
+/// [`RwLockWriteGuard<V>`]: https://doc.rust-lang.org/std/sync/struct.RwLockWriteGuard.html
 pub unsafe trait VecLike {
     type T;
 
@@ -61,11 +90,32 @@ pub unsafe trait VecLike {
     /// [`capacity`]: Self::capacity
     unsafe fn set_len(&mut self, new_len: usize);
 
+    /// Get [`spare_capacity_mut`], and the raw pointer of length field
+    ///
+    /// Inline storages, whose slots are inside [`Self`], should return the pointer,
+    /// then [`TailVec`] writes the length through it on drop,
+    /// without a `&mut Self` over the slots that may still be borrowed.
+    ///
+    /// Default returns [`None`], then [`set_len`] is used.
+    ///
+    /// # Safety
+    /// - The pointer must be valid for writes while the spare capacity is borrowed
+    /// - Writing `new_len` to it must be equivalent [`set_len`]
+    ///
+    /// [`spare_capacity_mut`]: Self::spare_capacity_mut
+    /// [`set_len`]: Self::set_len
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        (self.spare_capacity_mut(), None)
+    }
+
     /// Return collection is empty
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
 }
+#[cfg(all(feature = "alloc", not(feature = "allocator_api")))]
 unsafe impl<T> VecLike for Vec<T> {
     type T = T;
 
@@ -87,7 +137,73 @@ unsafe impl<T> VecLike for Vec<T> {
         }
     }
 }
-unsafe impl<T, V: VecLike<T = T>> VecLike for TailVec<'_, T, V> {
+#[cfg(all(feature = "alloc", feature = "allocator_api"))]
+unsafe impl<T, A: Allocator> VecLike for Vec<T, A> {
+    type T = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.capacity()
+    }
+
+    fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+        self.spare_capacity_mut()
+    }
+
+    unsafe fn set_len(&mut self, new_len: usize) {
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+}
+macro_rules! impl_forward_vec_like {
+    ($($(#[$meta:meta])* $ty:ty;)*) => {
+        $(
+            $(#[$meta])*
+            unsafe impl<V: VecLike + ?Sized> VecLike for $ty {
+                type T = V::T;
+
+                fn len(&self) -> usize {
+                    (**self).len()
+                }
+
+                fn capacity(&self) -> usize {
+                    (**self).capacity()
+                }
+
+                fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<Self::T>] {
+                    (**self).spare_capacity_mut()
+                }
+
+                fn spare_capacity_and_len_mut(&mut self)
+                -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+                {
+                    (**self).spare_capacity_and_len_mut()
+                }
+
+                unsafe fn set_len(&mut self, new_len: usize) {
+                    unsafe {
+                        (**self).set_len(new_len);
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_forward_vec_like! {
+    &mut V;
+    RefMut<'_, V>;
+    #[cfg(feature = "alloc")]
+    Box<V>;
+    #[cfg(feature = "std")]
+    std::sync::MutexGuard<'_, V>;
+    #[cfg(feature = "std")]
+    std::sync::RwLockWriteGuard<'_, V>;
+}
+unsafe impl<T, V: VecLike<T = T> + ?Sized> VecLike for TailVec<'_, T, V> {
     type T = T;
 
     fn len(&self) -> usize {
@@ -108,6 +224,15 @@ unsafe impl<T, V: VecLike<T = T>> VecLike for TailVec<'_, T, V> {
     unsafe fn set_len(&mut self, new_len: usize) {
         self.len = new_len;
     }
+
+    fn spare_capacity_and_len_mut(&mut self)
+    -> (&mut [MaybeUninit<Self::T>], Option<NonNull<usize>>)
+    {
+        let range = self.len..;
+        unsafe {
+            (&mut self.parts.as_mut()[range], Some(NonNull::from(&mut self.len)))
+        }
+    }
 }
 
 
@@ -160,10 +285,10 @@ impl<T: VecLike> SplitTail for T {
         let len = self.len();
         let mut vec = NonNull::from(self);
 
-        let datas = unsafe {
+        let (datas, len_ptr) = unsafe {
             let vec = vec.as_mut();
             vec.set_len(0);
-            vec.spare_capacity_mut()
+            vec.spare_capacity_and_len_mut()
         };
 
         let (left, rest)
@@ -171,7 +296,9 @@ impl<T: VecLike> SplitTail for T {
         let tailvec = TailVec {
             parts: rest.into(),
             len: len - mid,
+            mid,
             vec: Some(vec),
+            len_ptr,
             _phantom: PhantomData,
             _phantom_vec: PhantomData,
         };
@@ -183,22 +310,88 @@ impl<T: VecLike> SplitTail for T {
 /// [`Vec`] splitted tail part, create from [`split_tail`]
 ///
 /// [`split_tail`]: SplitTail::split_tail
-pub struct TailVec<'a, T, V: VecLike<T = T> = Vec<T>> {
+pub struct TailVec<
+    'a,
+    T,
+    #[cfg(feature = "alloc")] V: VecLike<T = T> + ?Sized = Vec<T>,
+    #[cfg(not(feature = "alloc"))] V: VecLike<T = T> + ?Sized,
+> {
     parts: NonNull<[MaybeUninit<T>]>,
     len: usize,
+    mid: usize,
     vec: Option<NonNull<V>>,
+    /// Length field of inline storage, see [`VecLike::spare_capacity_and_len_mut`]
+    len_ptr: Option<NonNull<usize>>,
     _phantom: PhantomData<&'a mut T>,
     _phantom_vec: PhantomData<&'a mut V>,
 }
-impl<T, V: VecLike<T = T>> Drop for TailVec<'_, T, V> {
+
+/// [`TailVec`] of [`Vec`] with custom allocator
+///
+/// # Examples
+/// ```
+/// #![feature(allocator_api)]
+/// # use tailvec::*;
+/// use std::alloc::Global;
+///
+/// fn push_one(tail: &mut AllocTailVec<'_, i32, Global>) {
+///     tail.push(1).unwrap();
+/// }
+///
+/// let mut vec = Vec::with_capacity_in(2, Global);
+/// vec.push(0);
+/// let (_, mut rest) = vec.split_tail(1);
+/// push_one(&mut rest);
+/// drop(rest);
+/// assert_eq!(vec, [0, 1]);
+/// ```
+#[cfg(all(feature = "alloc", feature = "allocator_api"))]
+pub type AllocTailVec<'a, T, A> = TailVec<'a, T, Vec<T, A>>;
+
+/// Type-erased [`TailVec`], create from [`erase`]
+///
+/// It has the full [`TailVec`] API,
+/// and the nested splits of it are still [`DynTailVec`] after [`erase`]
+///
+/// # Examples
+/// ```
+/// # use tailvec::*;
+/// fn push_all(tail: &mut DynTailVec<'_, i32>, values: &[i32]) {
+///     for &value in values {
+///         tail.push(value).unwrap();
+///     }
+/// }
+///
+/// let mut vec = Vec::with_capacity(6);
+/// vec.push(1);
+/// let (_, rest) = vec.split_tail(1);
+/// let mut rest = rest.erase();
+/// push_all(&mut rest, &[2, 3]);
+///
+/// let (_, rest1) = rest.split_tail(2);
+/// let mut rest1: DynTailVec<'_, _> = rest1.into();
+/// push_all(&mut rest1, &[4]);
+/// drop(rest1);
+///
+/// rest.retain(|n| n % 2 == 0);
+/// drop(rest);
+/// assert_eq!(vec, [1, 2, 4]);
+/// ```
+///
+/// [`erase`]: TailVec::erase
+pub type DynTailVec<'a, T> = TailVec<'a, T, dyn VecLike<T = T> + 'a>;
+
+impl<T, V: VecLike<T = T> + ?Sized> Drop for TailVec<'_, T, V> {
     #[track_caller]
     fn drop(&mut self) {
         let tail_cap = self.capacity();
         let tail_len = self.len();
-        let inner_capacity = self.vec_capacity();
-        let mid = inner_capacity - tail_cap;
+        let mid = self.mid;
 
-        if let Some(vec) = &mut self.vec {
+        if let Some(len_ptr) = self.len_ptr {
+            // Do not access the inner `V`, the head may still be borrowed
+            unsafe { len_ptr.write(mid + tail_len) }
+        } else if let Some(vec) = &mut self.vec {
             unsafe {
                 vec.as_mut().set_len(mid + tail_len);
             }
@@ -208,19 +401,44 @@ impl<T, V: VecLike<T = T>> Drop for TailVec<'_, T, V> {
         }
     }
 }
-impl<T, V: VecLike<T = T>> Default for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> Default for TailVec<'_, T, V> {
     fn default() -> Self {
         let parts = <&[_]>::default();
         Self {
             parts: parts.into(),
             len: 0,
+            mid: 0,
             vec: None,
+            len_ptr: None,
             _phantom: PhantomData,
             _phantom_vec: PhantomData,
         }
     }
 }
-impl<'a, T, V: VecLike<T = T>> TailVec<'a, T, V> {
+impl<'a, T, V: VecLike<T = T> + 'a> TailVec<'a, T, V> {
+    /// Erase the [`VecLike`] type, into [`DynTailVec`]
+    ///
+    /// *See [`DynTailVec`] for more documents*
+    #[must_use]
+    pub fn erase(self) -> DynTailVec<'a, T> {
+        let this = ManuallyDrop::new(self);
+        TailVec {
+            parts: this.parts,
+            len: this.len,
+            mid: this.mid,
+            vec: this.vec.map(|vec| vec as NonNull<dyn VecLike<T = T> + 'a>),
+            len_ptr: this.len_ptr,
+            _phantom: PhantomData,
+            _phantom_vec: PhantomData,
+        }
+    }
+}
+impl<'a, T, V: VecLike<T = T> + 'a> From<TailVec<'a, T, V>> for DynTailVec<'a, T> {
+    fn from(value: TailVec<'a, T, V>) -> Self {
+        value.erase()
+    }
+}
+impl<'a, T, V: VecLike<T = T> + ?Sized> TailVec<'a, T, V> {
     pub(crate) unsafe fn parts(&mut self) -> &mut [MaybeUninit<T>] {
         unsafe { self.parts.as_mut() }
     }
@@ -305,10 +523,8 @@ impl<'a, T, V: VecLike<T = T>> TailVec<'a, T, V> {
     /// assert_eq!(rest.vec_capacity(), 5);
     /// ```
     pub fn vec_capacity(&self) -> usize {
-        self.vec.as_ref()
-            .map(|ptr| unsafe { ptr.as_ref() })
-            .map(V::capacity)
-            .unwrap_or_default()
+        // Do not access the inner `V`, it may contain the parts inline
+        self.mid + self.capacity()
     }
 
     /// Get splitted point of [`VecLike`]
@@ -325,7 +541,7 @@ impl<'a, T, V: VecLike<T = T>> TailVec<'a, T, V> {
     /// ```
     #[must_use]
     pub fn split_point(&self) -> usize {
-        self.vec_capacity() - self.capacity()
+        self.mid
     }
 
     /// Get inner len of [`VecLike`]
@@ -809,14 +1025,14 @@ impl<'a, T, V: VecLike<T = T>> TailVec<'a, T, V> {
         }
     }
 }
-impl<T: Debug, V: VecLike<T = T>> Debug for TailVec<'_, T, V> {
+impl<T: Debug, V: VecLike<T = T> + ?Sized> Debug for TailVec<'_, T, V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.as_slice().fmt(f)
     }
 }
-impl<T: Eq, V: VecLike<T = T>> Eq for TailVec<'_, T, V> {
+impl<T: Eq, V: VecLike<T = T> + ?Sized> Eq for TailVec<'_, T, V> {
 }
-impl<T: PartialEq, V: VecLike<T = T>> PartialEq for TailVec<'_, T, V> {
+impl<T: PartialEq, V: VecLike<T = T> + ?Sized> PartialEq for TailVec<'_, T, V> {
     fn eq(&self, other: &Self) -> bool {
         self.as_slice() == other.as_slice()
     }
@@ -827,7 +1043,7 @@ impl<T: PartialEq, V: VecLike<T = T>> PartialEq for TailVec<'_, T, V> {
 }
 impl<T, U, V> PartialEq<[U]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &[U]) -> bool {
         self.as_slice() == other
@@ -839,7 +1055,7 @@ where T: PartialEq<U>,
 }
 impl<T, U, V> PartialEq<&'_ [U]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &&[U]) -> bool {
         self.as_slice() == *other
@@ -851,7 +1067,7 @@ where T: PartialEq<U>,
 }
 impl<T, U, V> PartialEq<&'_ mut [U]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &&mut [U]) -> bool {
         self.as_slice() == *other
@@ -863,7 +1079,7 @@ where T: PartialEq<U>,
 }
 impl<T, U, V, const N: usize> PartialEq<[U; N]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &[U; N]) -> bool {
         self.as_slice() == other
@@ -875,7 +1091,7 @@ where T: PartialEq<U>,
 }
 impl<T, U, V, const N: usize> PartialEq<&'_ [U; N]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &&[U; N]) -> bool {
         self.as_slice() == *other
@@ -887,7 +1103,7 @@ where T: PartialEq<U>,
 }
 impl<T, U, V, const N: usize> PartialEq<&'_ mut [U; N]> for TailVec<'_, T, V>
 where T: PartialEq<U>,
-      V: VecLike<T = T>,
+      V: VecLike<T = T> + ?Sized,
 {
     fn eq(&self, other: &&mut [U; N]) -> bool {
         self.as_slice() == *other
@@ -897,30 +1113,30 @@ where T: PartialEq<U>,
         self.as_slice() != *other
     }
 }
-impl<T: PartialOrd, V: VecLike<T = T>> PartialOrd for TailVec<'_, T, V> {
+impl<T: PartialOrd, V: VecLike<T = T> + ?Sized> PartialOrd for TailVec<'_, T, V> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.as_slice().partial_cmp(other.as_slice())
     }
 }
-impl<T: Ord, V: VecLike<T = T>> Ord for TailVec<'_, T, V> {
+impl<T: Ord, V: VecLike<T = T> + ?Sized> Ord for TailVec<'_, T, V> {
     fn cmp(&self, other: &Self) -> Ordering {
         self.as_slice().cmp(other.as_slice())
     }
 }
-impl<T, V: VecLike<T = T>> Deref for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> Deref for TailVec<'_, T, V> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
         self.as_slice()
     }
 }
-impl<T, V: VecLike<T = T>> DerefMut for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> DerefMut for TailVec<'_, T, V> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_slice_mut()
     }
 }
 impl<T, V, I> Index<I> for TailVec<'_, T, V>
-where V: VecLike<T = T>,
+where V: VecLike<T = T> + ?Sized,
       [T]: Index<I>,
 {
     type Output = <[T] as Index<I>>::Output;
@@ -930,39 +1146,39 @@ where V: VecLike<T = T>,
     }
 }
 impl<T, V, I> IndexMut<I> for TailVec<'_, T, V>
-where V: VecLike<T = T>,
+where V: VecLike<T = T> + ?Sized,
       [T]: IndexMut<I>,
 {
     fn index_mut(&mut self, index: I) -> &mut Self::Output {
         self.as_slice_mut().index_mut(index)
     }
 }
-impl<T, V: VecLike<T = T>> AsRef<[T]> for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> AsRef<[T]> for TailVec<'_, T, V> {
     fn as_ref(&self) -> &[T] {
         self.as_slice()
     }
 }
-impl<T, V: VecLike<T = T>> AsMut<[T]> for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> AsMut<[T]> for TailVec<'_, T, V> {
     fn as_mut(&mut self) -> &mut [T] {
         self.as_slice_mut()
     }
 }
-impl<T, V: VecLike<T = T>> Borrow<[T]> for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> Borrow<[T]> for TailVec<'_, T, V> {
     fn borrow(&self) -> &[T] {
         self.as_slice()
     }
 }
-impl<T, V: VecLike<T = T>> BorrowMut<[T]> for TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> BorrowMut<[T]> for TailVec<'_, T, V> {
     fn borrow_mut(&mut self) -> &mut [T] {
         self.as_slice_mut()
     }
 }
-impl<T: Hash, V: VecLike<T = T>> Hash for TailVec<'_, T, V> {
+impl<T: Hash, V: VecLike<T = T> + ?Sized> Hash for TailVec<'_, T, V> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.as_slice().hash(state);
     }
 }
-impl<'a, T, V: VecLike<T = T>> IntoIterator for TailVec<'a, T, V> {
+impl<'a, T, V: VecLike<T = T> + ?Sized> IntoIterator for TailVec<'a, T, V> {
     type Item = &'a mut T;
     type IntoIter = <&'a mut [T] as IntoIterator>::IntoIter;
 
@@ -970,7 +1186,7 @@ impl<'a, T, V: VecLike<T = T>> IntoIterator for TailVec<'a, T, V> {
         self.into_slice().iter_mut()
     }
 }
-impl<'a, T, V: VecLike<T = T>> IntoIterator for &'a TailVec<'_, T, V> {
+impl<'a, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'a TailVec<'_, T, V> {
     type Item = &'a T;
     type IntoIter = <&'a [T] as IntoIterator>::IntoIter;
 
@@ -978,7 +1194,7 @@ impl<'a, T, V: VecLike<T = T>> IntoIterator for &'a TailVec<'_, T, V> {
         self.as_slice().iter()
     }
 }
-impl<'a, T, V: VecLike<T = T>> IntoIterator for &'a mut TailVec<'_, T, V> {
+impl<'a, T, V: VecLike<T = T> + ?Sized> IntoIterator for &'a mut TailVec<'_, T, V> {
     type Item = &'a mut T;
     type IntoIter = <&'a mut [T] as IntoIterator>::IntoIter;
 
@@ -986,7 +1202,7 @@ impl<'a, T, V: VecLike<T = T>> IntoIterator for &'a mut TailVec<'_, T, V> {
         self.as_slice_mut().iter_mut()
     }
 }
-impl<T, V: VecLike<T = T>> Extend<T> for &mut TailVec<'_, T, V> {
+impl<T, V: VecLike<T = T> + ?Sized> Extend<T> for &mut TailVec<'_, T, V> {
     /// Extends a collection with the contents of an iterator.
     ///
     /// # Panics
@@ -1003,27 +1219,27 @@ impl<T, V: VecLike<T = T>> Extend<T> for &mut TailVec<'_, T, V> {
     }
 }
 impl<T, V> UnwindSafe for TailVec<'_, T, V>
-where V: UnwindSafe + RefUnwindSafe + VecLike<T = T>,
+where V: UnwindSafe + RefUnwindSafe + VecLike<T = T> + ?Sized,
       T: UnwindSafe + RefUnwindSafe,
 {
 }
 impl<T, V> RefUnwindSafe for TailVec<'_, T, V>
-where V: RefUnwindSafe + VecLike<T = T>,
+where V: RefUnwindSafe + VecLike<T = T> + ?Sized,
       T: RefUnwindSafe,
 {
 }
 unsafe impl<T, V> Send for TailVec<'_, T, V>
-where V: Send + VecLike<T = T>,
+where V: Send + VecLike<T = T> + ?Sized,
       T: Send,
 {
 }
 unsafe impl<T, V> Sync for TailVec<'_, T, V>
-where V: Sync + VecLike<T = T>,
+where V: Sync + VecLike<T = T> + ?Sized,
       T: Sync,
 {
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use super::*;
 
@@ -794,3 +794,1714 @@ fn _borrow_sign_test<'a, T>(x: &'a mut TailVec<'a, T>) -> &'a mut [T] {
 fn _borrow_sign_test2<'a, 'b: 'a, T>(x: &'a mut TailVec<'b, T>) -> &'a mut [T] {
     x.as_slice_mut()
 }
+
+#[test]
+fn push_int_test() {
+    let mut vec = vec![0xff];
+    vec.reserve_exact(7);
+    let (_, mut rest) = vec.split_tail(1);
+    assert_eq!(rest.put_u16_le(0x0102), Ok(()));
+    assert_eq!(rest.put_u16_be(0x0102), Ok(()));
+    assert_eq!(rest.put_i8(-1), Ok(()));
+    assert_eq!(rest, [0x02, 0x01, 0x01, 0x02, 0xff]);
+    assert_eq!(rest.put_u32_be(1), Err(1));
+    assert_eq!(rest, [0x02, 0x01, 0x01, 0x02, 0xff]);
+    assert_eq!(rest.put_u16_le(3), Ok(()));
+    assert_eq!(rest.put_u8(4), Err(4));
+    drop(rest);
+    assert_eq!(vec, [0xff, 0x02, 0x01, 0x01, 0x02, 0xff, 3, 0]);
+}
+
+#[test]
+fn push_float_test() {
+    let mut vec = Vec::with_capacity(12);
+    let (_, mut rest) = vec.split_tail(0);
+    assert_eq!(rest.put_f32_le(1.5), Ok(()));
+    assert_eq!(rest.put_f64_be(-2.0), Ok(()));
+    assert_eq!(rest.put_f32_le(1.5), Err(1.5));
+    drop(rest);
+    assert_eq!(vec[..4], 1.5f32.to_le_bytes());
+    assert_eq!(vec[4..], (-2.0f64).to_be_bytes());
+}
+
+#[test]
+fn push_varint_test() {
+    let mut vec = Vec::with_capacity(20);
+    let (_, mut rest) = vec.split_tail(0);
+    assert_eq!(rest.put_uvarint(u64::MAX), Ok(()));
+    assert_eq!(rest.len(), 10);
+    assert_eq!(rest[9], 0x01);
+    assert_eq!(rest.put_ivarint(i64::MIN), Ok(()));
+    assert_eq!(rest.len(), 20);
+    assert_eq!(rest[10..], rest[..10]);
+    assert_eq!(rest.put_uvarint(0), Err(0));
+    rest.truncate(11);
+    assert_eq!(rest.put_uvarint(u64::MAX), Err(u64::MAX));
+    assert_eq!(rest.len(), 11);
+}
+
+#[test]
+fn length_prefix_test() {
+    let mut vec = vec![9];
+    vec.reserve_exact(9);
+    let (_, mut rest) = vec.split_tail(1);
+    {
+        let Ok(mut outer) = rest.reserve_length_prefix(|n| [n as u8]) else {
+            unreachable!()
+        };
+        let Ok(mut inner) = outer.reserve_length_prefix(|n| [n as u8]) else {
+            unreachable!()
+        };
+        inner.put_slice(b"ab").unwrap();
+        assert_eq!(inner.written(), 2);
+        drop(inner);
+        outer.put_u8(b'c').unwrap();
+        assert_eq!(outer.written(), 4);
+    }
+    assert_eq!(rest, [4, 2, b'a', b'b', b'c']);
+
+    let encode = |n: usize| (n as u64).to_le_bytes();
+    assert!(rest.reserve_length_prefix(encode).is_err());
+    assert_eq!(rest.len(), 5);
+
+    let Ok(mut msg) = rest.reserve_length_prefix(|n| [n as u8, 0]) else {
+        unreachable!()
+    };
+    msg.pop().unwrap();
+    drop(msg);
+    assert_eq!(rest, [4, 2, b'a', b'b', b'c']);
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn bytes_buf_mut_test() {
+    use bytes::BufMut;
+
+    let mut vec = Vec::with_capacity(7);
+    let (_, mut rest) = vec.split_tail(0);
+    assert_eq!(rest.remaining_mut(), 7);
+    rest.put_u16(0x0102);
+    BufMut::put_slice(&mut rest, b"abc");
+    assert_eq!(rest.remaining_mut(), 2);
+    // the inherent methods are capacity-checked
+    assert_eq!(rest.put_slice(b"def"), Err(&b"def"[..]));
+    assert_eq!(rest.put_u16_le(0x0403), Ok(()));
+    assert_eq!(rest.put_u8(5), Err(5));
+    drop(rest);
+    assert_eq!(vec, [1, 2, b'a', b'b', b'c', 3, 4]);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_serialize_test() {
+    use serde_test::{assert_ser_tokens, Token};
+
+    let mut vec = vec![1, 2, 3];
+    let (_, rest) = vec.split_tail(1);
+    assert_ser_tokens(&rest, &[
+        Token::Seq { len: Some(2) },
+        Token::I32(2),
+        Token::I32(3),
+        Token::SeqEnd,
+    ]);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_deserialize_into_test() {
+    use serde::de::value::{Error, SeqDeserializer};
+
+    let mut vec = vec![0u8];
+    vec.reserve_exact(3);
+    let (_, mut rest) = vec.split_tail(1);
+
+    let de = SeqDeserializer::<_, Error>::new([1, 2].into_iter());
+    rest.deserialize_into(de).unwrap();
+    assert_eq!(rest, [1, 2]);
+
+    // no size hint, overflow when push
+    let de = SeqDeserializer::<_, Error>::new([3, 4].into_iter().filter(|_| true));
+    let err = rest.deserialize_into(de).unwrap_err();
+    assert!(err.to_string().contains("overflow of capacity"), "{err}");
+    assert_eq!(rest, [1, 2]);
+
+    // element error
+    rest.truncate(1);
+    let de = SeqDeserializer::<_, Error>::new([3, 300].into_iter());
+    rest.deserialize_into(de).unwrap_err();
+    assert_eq!(rest, [1]);
+
+    let de = SeqDeserializer::<_, Error>::new([3, 4].into_iter());
+    rest.deserialize_into(de).unwrap();
+    drop(rest);
+    assert_eq!(vec, [0, 1, 3, 4]);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn pod_bytes_test() {
+    let mut vec = vec![[0f32; 4]];
+    vec.reserve_exact(2);
+    let (_, mut rest) = vec.split_tail(1);
+    assert_eq!(rest.spare_bytes_mut().len(), 32);
+
+    let mut bytes = [0u8; 49];
+    bytes[1..17].copy_from_slice(bytemuck::cast_slice(&[1f32, 2., 3., 4.]));
+    assert!(rest.extend_from_bytes(&bytes[1..16]).is_err());
+    assert!(rest.extend_from_bytes(&bytes[1..]).is_err());
+    assert_eq!(rest.len(), 0);
+    assert_eq!(rest.extend_from_bytes(&bytes[1..17]), Ok(()));
+    assert_eq!(rest, [[1., 2., 3., 4.]]);
+    assert_eq!(rest.as_bytes(), &bytes[1..17]);
+    assert_eq!(rest.spare_bytes_mut().len(), 16);
+
+    rest.as_bytes_mut().fill(0);
+    drop(rest);
+    assert_eq!(vec, [[0.; 4], [0.; 4]]);
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn pod_zst_bytes_test() {
+    let mut vec = vec![(); 2];
+    let (_, mut rest) = vec.split_tail(1);
+    assert_eq!(rest.as_bytes(), &[]);
+    assert_eq!(rest.spare_bytes_mut().len(), 0);
+    assert_eq!(rest.extend_from_bytes(&[]), Ok(()));
+    assert!(rest.extend_from_bytes(&[0]).is_err());
+    assert_eq!(rest.len(), 1);
+}
+
+#[test]
+fn array_storage_test() {
+    let mut vec = ArrayVecStorage::<Box<i32>, 5>::new();
+    vec.push(Box::new(1)).unwrap();
+    vec.push(Box::new(2)).unwrap();
+    vec.push(Box::new(3)).unwrap();
+    let (left, mut rest) = vec.split_tail(1);
+    assert_eq!(left, [Box::new(1)]);
+    assert_eq!(rest.capacity(), 4);
+    assert_eq!(rest.vec_capacity(), 5);
+    assert_eq!(rest.split_point(), 1);
+    rest.push(Box::new(4)).unwrap();
+    rest.push(Box::new(5)).unwrap();
+    assert_eq!(rest.push(Box::new(6)), Err(Box::new(6)));
+    assert_eq!(rest.remove(0), Box::new(2));
+    rest.retain(|x| **x != 4);
+    drop(rest);
+    *left[0] += 10; // The head is still borrowed after the tail dropped
+    assert_eq!(vec.as_slice(), [Box::new(11), Box::new(3), Box::new(5)]);
+    assert_eq!(vec.pop(), Some(Box::new(5)));
+    assert_eq!(vec.len(), 2);
+}
+
+#[test]
+fn array_storage_nested_test() {
+    let mut vec = ArrayVecStorage::<i32, 4>::new();
+    vec.push(1).unwrap();
+    let (_, mut rest) = vec.split_tail(0);
+    let (_, mut rest1) = rest.split_tail(1);
+    rest1.push(2).unwrap();
+    assert_eq!(rest1.vec_capacity(), 4);
+    drop(rest1);
+    rest.push(3).unwrap();
+    drop(rest);
+    assert_eq!(vec, [1, 2, 3]);
+}
+
+#[test]
+fn slice_storage_test() {
+    use core::mem::MaybeUninit;
+
+    let mut buf = [const { MaybeUninit::uninit() }; 3];
+    let mut vec = SliceStorage::new(&mut buf);
+    let (_, mut rest) = vec.split_tail(0);
+    rest.push(String::from("a")).unwrap();
+    rest.push(String::from("b")).unwrap();
+    rest.push(String::from("c")).unwrap();
+    assert!(rest.push(String::from("d")).is_err());
+    rest.drain(..1);
+    drop(rest);
+    assert_eq!(vec.as_slice(), ["b", "c"]);
+    let slice = vec.into_slice();
+    assert_eq!(slice, ["b", "c"]);
+    unsafe { core::ptr::drop_in_place(slice) }
+}
+
+fn backend_suite<V, F>(mut new: F)
+where V: VecLike<T = i32> + core::ops::Deref<Target = [i32]>,
+      F: FnMut(&[i32]) -> V,
+{
+    assert!(new(&[]).capacity() >= 6);
+
+    // push and pop
+    let mut vec = new(&[0, 1, 2]);
+    let cap = vec.capacity();
+    let (left, mut rest) = vec.split_tail(1);
+    assert_eq!(left, [0]);
+    assert_eq!(rest, [1, 2]);
+    assert_eq!(rest.capacity(), cap - 1);
+    assert_eq!(rest.pop(), Some(2));
+    for i in 2..cap as i32 {
+        assert_eq!(rest.push(i), Ok(()));
+    }
+    assert_eq!(rest.push(-1), Err(-1));
+    assert_eq!(rest.len(), cap - 1);
+    drop(rest);
+    assert_eq!(*vec, (0..cap as i32).collect::<Vec<_>>());
+
+    // insert and remove
+    let mut vec = new(&[0, 1, 2, 3]);
+    let (_, mut rest) = vec.split_tail(2);
+    assert_eq!(rest.insert(0, 9), Ok(()));
+    assert_eq!(rest.remove(1), 2);
+    assert_eq!(rest.swap_remove(0), 9);
+    drop(rest);
+    assert_eq!(*vec, [0, 1, 3]);
+
+    // drain
+    let mut vec = new(&[0, 1, 2, 3, 4, 5]);
+    let (_, mut rest) = vec.split_tail(1);
+    assert_eq!(rest.drain(1..3).collect::<Vec<_>>(), [2, 3]);
+    let mut drain = rest.drain(1..);
+    assert_eq!(drain.next_back(), Some(5));
+    drop(drain);
+    drop(rest);
+    assert_eq!(*vec, [0, 1]);
+
+    // retain
+    let mut vec = new(&[0, 1, 2, 3, 4, 5]);
+    let (_, mut rest) = vec.split_tail(1);
+    rest.retain(|n| n % 2 == 1);
+    assert_eq!(rest, [1, 3, 5]);
+    rest.retain_mut(|n| { *n *= 2; *n != 6 });
+    drop(rest);
+    assert_eq!(*vec, [0, 2, 10]);
+
+    // nested
+    let mut vec = new(&[0, 1, 2]);
+    let (_, mut rest) = vec.split_tail(1);
+    let (_, mut rest1) = rest.split_tail(1);
+    rest1.push(3).unwrap();
+    drop(rest1);
+    rest.truncate(1);
+    drop(rest);
+    assert_eq!(*vec, [0, 1]);
+}
+
+#[test]
+fn vec_backend_test() {
+    backend_suite(|s| {
+        let mut vec = Vec::with_capacity(8);
+        vec.extend_from_slice(s);
+        vec
+    });
+}
+
+/// Use the head slice after the tail is dropped, `vec` is `["a"]`
+#[cfg(any(feature = "arrayvec", feature = "heapless", feature = "smallvec"))]
+fn inline_head_suite<V: InlineVec<T = String>>(vec: V) -> V {
+    let mut storage = InlineStorage::new(vec);
+    let (left, mut rest) = storage.split_tail(1);
+    rest.push("b".into()).unwrap();
+    drop(rest);
+    left[0].push('!');
+    assert_eq!(left, ["a!"]);
+
+    let (left, mut rest) = storage.split_tail(1);
+    assert_eq!(rest.pop().as_deref(), Some("b"));
+    drop(rest);
+    left[0].push('?');
+    assert_eq!(*storage, ["a!?"]);
+    storage.into_inner()
+}
+
+#[test]
+#[cfg(feature = "arrayvec")]
+fn arrayvec_backend_test() {
+    use arrayvec::ArrayVec;
+
+    backend_suite(|s| InlineStorage::new(s.iter().copied().collect::<ArrayVec<_, 8>>()));
+    push_pop_suite(&mut InlineStorage::new(ArrayVec::<_, 5>::new()), Some(5));
+
+    let mut vec = ArrayVec::<String, 4>::new();
+    vec.push("a".into());
+    let vec = inline_head_suite(vec);
+    assert_eq!(vec.as_slice(), ["a!?"]);
+    let mut vec = ArrayVec::<String, 4>::new();
+    vec.push("a".into());
+    inline_head_suite(&mut vec);
+    assert_eq!(vec.as_slice(), ["a!?"]);
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn heapless_backend_test() {
+    backend_suite(|s| InlineStorage::new(heapless::Vec::<_, 8>::from_slice(s).unwrap()));
+    backend_suite(|s| InlineStorage::new(heapless::Vec::<_, 8, u8>::from_slice(s).unwrap()));
+    push_pop_suite(&mut InlineStorage::new(heapless::Vec::<_, 5>::new()), Some(5));
+
+    let mut vec = heapless::Vec::<String, 4>::new();
+    vec.push("a".into()).unwrap();
+    inline_head_suite(&mut vec);
+    assert_eq!(vec.as_slice(), ["a!?"]);
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn smallvec_backend_test() {
+    use smallvec::SmallVec;
+
+    backend_suite(|s| InlineStorage::new(SmallVec::<[_; 8]>::from_slice(s)));
+    push_pop_suite(&mut InlineStorage::new(SmallVec::<[_; 5]>::new()), Some(5));
+    backend_suite(|s| {
+        let mut vec = SmallVec::<[_; 2]>::with_capacity(8);
+        vec.extend_from_slice(s);
+        InlineStorage::new(vec)
+    });
+}
+
+#[test]
+#[cfg(feature = "smallvec")]
+fn smallvec_inline_split_test() {
+    use smallvec::SmallVec;
+
+    let mut vec = SmallVec::<[String; 4]>::new();
+    vec.push("a".into());
+    vec.push("b".into());
+    assert!(!vec.spilled());
+    let inline_ptr = vec.as_ptr();
+
+    let mut storage = InlineStorage::new(&mut vec);
+    let (left, mut rest) = storage.split_tail(1);
+    assert_eq!(left, ["a"]);
+    assert_eq!(rest.capacity(), 3);
+    rest.push("c".into()).unwrap();
+    rest.push("d".into()).unwrap();
+    assert_eq!(rest.push("e".into()), Err("e".into()));
+    assert_eq!(rest.remove(0), "b");
+    drop(rest);
+    left[0].push('!');
+    assert_eq!(left, ["a!"]);
+    drop(storage);
+
+    assert!(!vec.spilled());
+    assert_eq!(vec.as_ptr(), inline_ptr);
+    assert_eq!(vec.as_slice(), ["a!", "c", "d"]);
+
+    vec.push("e".into());
+    vec.push("f".into());
+    assert!(vec.spilled());
+    let mut storage = InlineStorage::new(&mut vec);
+    let (left, mut rest) = storage.split_tail(5);
+    assert_eq!(rest.push("g".into()), Ok(()));
+    drop(rest);
+    left[4].push('?');
+    drop(storage);
+    assert_eq!(vec.as_slice(), ["a!", "c", "d", "e", "f?", "g"]);
+
+    let mut vec = SmallVec::<[String; 2]>::new();
+    vec.push("a".into());
+    let vec = inline_head_suite(vec);
+    assert_eq!(vec.as_slice(), ["a!?"]);
+}
+
+#[test]
+fn deque_wrapped_split_test() {
+    use std::collections::VecDeque;
+
+    let mut deque = VecDeque::with_capacity(6);
+    deque.extend(["a", "b", "c", "d"].map(String::from));
+    deque.pop_front();
+    deque.pop_front();
+    deque.push_back("e".into());
+    deque.push_back("f".into());
+    deque.push_back("g".into());
+    assert!(!deque.as_slices().1.is_empty());
+    let cap = deque.capacity();
+
+    let mut vec = DequeVec::from(&mut deque);
+    let ptr = vec.as_ptr();
+    assert_eq!(vec.capacity(), cap);
+    let (left, mut rest) = vec.split_tail(2);
+    assert_eq!(left, ["c", "d"]);
+    assert_eq!(rest, ["e", "f", "g"]);
+    while rest.push("x".into()).is_ok() {}
+    assert_eq!(rest.len(), cap - 2);
+    rest.remove(0);
+    drop(rest);
+    drop(vec);
+
+    assert_eq!(deque.capacity(), cap);
+    assert_eq!(deque.as_slices().0.as_ptr(), ptr);
+    assert_eq!(deque.len(), cap - 1);
+    assert_eq!(deque.range(..4).collect::<Vec<_>>(), ["c", "d", "f", "g"]);
+    deque.push_front("b".into());
+    assert_eq!(deque.front().unwrap(), "b");
+}
+
+#[test]
+#[cfg(feature = "bumpalo")]
+fn bumpalo_backend_test() {
+    let bump = bumpalo::Bump::new();
+    backend_suite(|s| {
+        let mut vec = bumpalo::collections::Vec::with_capacity_in(8, &bump);
+        vec.extend_from_slice(s);
+        vec
+    });
+    push_pop_suite(&mut bumpalo::collections::Vec::new_in(&bump), None);
+}
+
+#[test]
+#[cfg(feature = "allocator_api")]
+fn allocator_api_backend_test() {
+    use std::alloc::System;
+
+    backend_suite(|s| {
+        let mut vec = Vec::with_capacity_in(8, System);
+        vec.extend_from_slice(s);
+        vec
+    });
+
+    let mut vec = Vec::with_capacity_in(4, System);
+    vec.push(Box::new(1));
+    let (_, mut rest): (_, AllocTailVec<'_, _, System>) = vec.split_tail(0);
+    rest.push(Box::new(2)).unwrap();
+    drop(rest);
+    assert_eq!(vec, [Box::new(1), Box::new(2)]);
+}
+
+#[test]
+fn forward_vec_like_test() {
+    use std::{cell::RefCell, sync::{Mutex, RwLock}};
+
+    fn generic_push<V: VecLike<T = i32>>(vec: &mut V, value: i32) {
+        let len = vec.len();
+        let (_, mut rest) = vec.split_tail(len);
+        rest.push(value).unwrap();
+    }
+
+    let mut vec = Vec::with_capacity(8);
+    generic_push(&mut &mut vec, 1);
+    assert_eq!(vec, [1]);
+
+    let mut boxed = Box::new(vec);
+    generic_push(&mut boxed, 2);
+    let (_, mut rest) = boxed.split_tail(1);
+    rest.push(3).unwrap();
+    drop(rest);
+    assert_eq!(*boxed, [1, 2, 3]);
+
+    let cell = RefCell::new(*boxed);
+    generic_push(&mut cell.borrow_mut(), 4);
+    let mut guard = cell.borrow_mut();
+    let (left, mut rest) = guard.split_tail(2);
+    assert_eq!(left, [1, 2]);
+    assert_eq!(rest.pop(), Some(4));
+    drop(rest);
+    drop(guard);
+    assert_eq!(*cell.borrow(), [1, 2, 3]);
+
+    let mutex = Mutex::new(cell.into_inner());
+    let mut guard = mutex.lock().unwrap();
+    let (_, mut rest) = guard.split_tail(3);
+    rest.push(5).unwrap();
+    drop(rest);
+    drop(guard);
+    generic_push(&mut mutex.lock().unwrap(), 6);
+    assert_eq!(*mutex.lock().unwrap(), [1, 2, 3, 5, 6]);
+
+    let lock = RwLock::new(mutex.into_inner().unwrap());
+    generic_push(&mut lock.write().unwrap(), 7);
+    assert_eq!(*lock.read().unwrap(), [1, 2, 3, 5, 6, 7]);
+}
+
+#[test]
+fn dyn_tailvec_test() {
+    fn fill(tail: &mut DynTailVec<'_, String>, n: usize) {
+        for i in 0..n {
+            tail.push(i.to_string()).unwrap();
+        }
+    }
+
+    let mut vec = Vec::with_capacity(8);
+    vec.push(String::from("a"));
+    let (_, rest) = vec.split_tail(1);
+    let mut rest = rest.erase();
+    fill(&mut rest, 4);
+    assert_eq!(rest.drain(1..3).collect::<Vec<_>>(), ["1", "2"]);
+    assert_eq!(rest, ["0", "3"]);
+    assert_eq!(rest.split_point(), 1);
+    assert_eq!(rest.vec_capacity(), 8);
+
+    let (_, rest1) = rest.split_tail(1);
+    let mut rest1 = rest1.erase();
+    fill(&mut rest1, 2);
+    rest1.retain(|s| s != "0");
+    let (_, rest2) = rest1.split_tail(0);
+    let mut rest2 = rest2.erase();
+    assert_eq!(rest2, ["3", "1"]);
+    assert_eq!(rest2.swap_remove(0), "3");
+    drop(rest2);
+    drop(rest1);
+    drop(rest);
+    assert_eq!(vec, ["a", "0", "1"]);
+
+    let mut storage = ArrayVecStorage::<String, 2>::new();
+    let (_, rest) = storage.split_tail(0);
+    let mut rest = DynTailVec::from(rest);
+    fill(&mut rest, 2);
+    assert!(rest.push(String::new()).is_err());
+    drop(rest);
+    assert_eq!(storage.as_slice(), ["0", "1"]);
+}
+
+#[test]
+fn dyn_tailvec_default_test() {
+    let val: DynTailVec<'_, i32> = TailVec::default();
+    assert_eq!(val.len(), 0);
+    assert_eq!(val.capacity(), 0);
+    assert_eq!(val.vec_capacity(), 0);
+    let val: DynTailVec<'_, i32> = TailVec::<i32>::default().erase();
+    assert!(val.is_empty());
+}
+
+fn push_pop_suite<S: PushPop<T = i32>>(stack: &mut S, cap: Option<usize>) {
+    stack.truncate(0);
+    assert!(stack.is_empty());
+    let n = cap.unwrap_or(10) as i32;
+    assert_eq!(stack.try_extend(0..n), Ok(()));
+    assert_eq!(stack.len(), n as usize);
+    if cap.is_some() {
+        assert_eq!(stack.remaining_capacity(), 0);
+        assert_eq!(stack.try_push(n), Err(n));
+        assert_eq!(stack.try_extend([n, n+1]), Err(n));
+    } else {
+        assert!(stack.remaining_capacity() > 0);
+    }
+    assert_eq!(stack.pop(), Some(n - 1));
+    assert_eq!(stack.remaining_capacity(), cap.map_or(usize::MAX - stack.len(), |_| 1));
+    assert_eq!(stack.drain(1..3).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(stack.len(), n as usize - 3);
+    let mut drain = stack.drain(1..);
+    assert_eq!(drain.next(), Some(3));
+    drop(drain);
+    assert_eq!(stack.len(), 1);
+    stack.truncate(0);
+    assert_eq!(stack.pop(), None);
+}
+
+#[test]
+fn push_pop_test() {
+    use core::mem::MaybeUninit;
+
+    let mut vec = Vec::new();
+    push_pop_suite(&mut vec, None);
+
+    let mut vec = Vec::with_capacity(7);
+    vec.push(-1);
+    let (_, mut rest) = vec.split_tail(1);
+    push_pop_suite(&mut rest, Some(6));
+    push_pop_suite(&mut rest.erase(), Some(6));
+
+    push_pop_suite(&mut ArrayVecStorage::<i32, 5>::new(), Some(5));
+
+    let mut buf = [const { MaybeUninit::uninit() }; 5];
+    push_pop_suite(&mut SliceStorage::new(&mut buf), Some(5));
+}
+
+#[test]
+fn storage_drain_test() {
+    let mut vec = ArrayVecStorage::<Box<i32>, 6>::new();
+    for i in 0..6 {
+        vec.push(Box::new(i)).unwrap();
+    }
+    let mut drain = PushPop::drain(&mut vec, 1..4);
+    assert_eq!(drain.as_slice(), [Box::new(1), Box::new(2), Box::new(3)]);
+    assert_eq!(drain.next_back(), Some(Box::new(3)));
+    drop(drain);
+    assert_eq!(vec.as_slice(), [Box::new(0), Box::new(4), Box::new(5)]);
+
+    let mut vec = ArrayVecStorage::<(), 6>::new();
+    vec.try_extend([(); 4]).unwrap();
+    assert_eq!(PushPop::drain(&mut vec, ..3).count(), 3);
+    assert_eq!(vec.len(), 1);
+
+    // Leaked drain keeps the elements before the range
+    let mut vec = ArrayVecStorage::<i32, 6>::new();
+    vec.try_extend(0..6).unwrap();
+    forget(PushPop::drain(&mut vec, 2..4));
+    assert_eq!(vec.as_slice(), [0, 1]);
+
+    let mut vec = vec![0, 1, 2, 3, 4];
+    let (_, mut rest) = vec.split_tail(1);
+    forget(rest.drain(2..));
+    assert_eq!(rest, [1, 2]);
+}
+
+#[test]
+fn raw_storage_callback_test() {
+    use core::ffi::{c_int, c_void};
+
+    type Tail<'a, 'b> = TailVec<'a, String, RawStorage<'b, String>>;
+
+    // C-style producer, calls back for each value until it fails
+    extern "C" fn produce(
+        cb: extern "C" fn(*mut c_void, c_int) -> c_int,
+        ctx: *mut c_void,
+    ) -> c_int {
+        let mut count = 0;
+        while cb(ctx, count) == 0 {
+            count += 1;
+        }
+        count
+    }
+    extern "C" fn append(ctx: *mut c_void, value: c_int) -> c_int {
+        let tail = unsafe { &mut *ctx.cast::<Tail<'_, '_>>() };
+        tail.push(value.to_string()).map_or(-1, |()| 0)
+    }
+
+    // foreign owned buffer
+    let mut owner = std::mem::ManuallyDrop::new(Vec::<String>::with_capacity(5));
+    owner.push("head".into());
+    let (ptr, mut len, cap) = (owner.as_mut_ptr(), owner.len(), owner.capacity());
+
+    {
+        let mut storage = unsafe { RawStorage::from_raw_parts(ptr, &mut len, cap) };
+        assert_eq!(storage.len(), 1);
+        assert_eq!(storage.capacity(), cap);
+        let (head, mut rest) = storage.split_tail(1);
+        assert_eq!(head, ["head"]);
+        let ctx = (&mut rest as *mut Tail<'_, '_>).cast::<c_void>();
+        assert_eq!(produce(append, ctx), cap as c_int - 1);
+        assert_eq!(rest.len(), cap - 1);
+        assert_eq!(rest.remove(0), "0");
+        drop(rest);
+        assert_eq!(storage.len(), cap - 1);
+        assert_eq!(PushPop::pop(&mut storage), Some((cap - 2).to_string()));
+    }
+    assert_eq!(len, cap - 2);
+
+    let vec = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+    assert_eq!(vec[..3], ["head", "1", "2"]);
+}
+
+#[test]
+fn raw_storage_empty_test() {
+    let mut len = 0;
+    let mut storage = unsafe {
+        RawStorage::<i32>::from_raw_parts(core::ptr::null_mut(), &mut len, 0)
+    };
+    let (_, mut rest) = storage.split_tail(0);
+    assert_eq!(rest.push(1), Err(1));
+    drop(rest);
+    assert_eq!(storage.as_slice(), []);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_extend_from_fn_test() {
+    let mut vec = vec![String::from("header")];
+    vec.reserve_exact(10_000);
+    let (_, mut rest) = vec.split_tail(1);
+
+    assert!(rest.par_extend_from_fn(10_000, |i| i.to_string()).is_ok());
+    assert!(rest.par_extend_from_fn(1, |i| i.to_string()).is_err());
+    assert_eq!(rest.len(), 10_000);
+    assert!(rest.iter().enumerate().all(|(i, s)| *s == i.to_string()));
+
+    rest.truncate(10);
+    assert!(rest.par_extend_from_fn(0, |_| unreachable!()).is_ok());
+    assert_eq!(rest.len(), 10);
+    drop(rest);
+    assert_eq!(vec.len(), 11);
+    assert_eq!(vec[0], "header");
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_extend_from_fn_panic_test() {
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+    struct Counted;
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, SeqCst);
+        }
+    }
+
+    let mut vec = Vec::with_capacity(1000);
+    vec.push(Counted);
+    let (_, mut rest) = vec.split_tail(1);
+    let created = AtomicUsize::new(0);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        rest.par_extend_from_fn(999, |i| {
+            assert_ne!(i, 500);
+            created.fetch_add(1, SeqCst);
+            Counted
+        }).ok();
+    }));
+    assert!(res.is_err());
+    assert_eq!(rest.len(), 0);
+    assert_eq!(DROPPED.load(SeqCst), created.load(SeqCst));
+    drop(rest);
+    assert_eq!(vec.len(), 1);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_extend_test() {
+    use rayon::prelude::*;
+
+    let mut vec = Vec::with_capacity(100);
+    vec.push(-1);
+    let (_, mut rest) = vec.split_tail(1);
+
+    (&mut rest).par_extend((0..50).into_par_iter());
+    (&mut rest).par_extend((0..100).into_par_iter().filter(|n| n % 10 == 0));
+    assert_eq!(rest.len(), 60);
+    assert_eq!(rest[..3], [0, 1, 2]);
+    assert_eq!(rest[50..], [0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        (&mut rest).par_extend((0..40).into_par_iter());
+    }));
+    assert!(res.is_err());
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        (&mut rest).par_extend((0..40).into_par_iter().filter(|_| true));
+    }));
+    assert!(res.is_err());
+    assert_eq!(rest.len(), 60);
+
+    (&mut rest).par_extend(vec![7; 39]);
+    assert_eq!(rest.len(), 99);
+    drop(rest);
+    assert_eq!(vec[0], -1);
+    assert_eq!(vec.len(), 100);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_retain_test() {
+    let mut vec = (0..100_000).map(|n| n.to_string()).collect::<Vec<_>>();
+    let (_, mut rest) = vec.split_tail(3);
+
+    rest.par_retain(|s| s.ends_with('7'));
+    let expected = (3..100_000)
+        .filter(|n| n % 10 == 7)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(rest, expected[..]);
+
+    rest.par_retain_mut(|s| {
+        s.push('!');
+        false
+    });
+    assert!(rest.is_empty());
+    drop(rest);
+    assert_eq!(vec, ["0", "1", "2"]);
+
+    let mut vec = vec![String::new(); 3];
+    let (_, mut rest) = vec.split_tail(1);
+    rest.par_retain(|_| true);
+    assert_eq!(rest.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_retain_panic_test() {
+    let mut vec = (0..10_000).collect::<Vec<_>>();
+    let (_, mut rest) = vec.split_tail(0);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        rest.par_retain(|&n| {
+            assert_ne!(n, 5000);
+            n % 2 == 0
+        });
+    }));
+    assert!(res.is_err());
+    assert!(rest.contains(&5000));
+    assert!(rest.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn spsc_wrap_test() {
+    // 3 ring slots, and the ring state of 8 words
+    let mut vec = Vec::<u64>::with_capacity(5 + size_of::<usize>());
+    vec.extend([1, 2]);
+
+    let (mut tx, mut rx) = vec.split_spsc().unwrap();
+    assert_eq!(tx.capacity(), 3);
+    assert_eq!(rx.pop(), None);
+
+    for i in 0..100 {
+        tx.push(i).unwrap();
+        tx.push(i + 1000).unwrap();
+        assert_eq!(rx.pop(), Some(i));
+        assert_eq!(rx.pop(), Some(i + 1000));
+        tx.push(i + 2000).unwrap();
+        assert_eq!(rx.pop(), Some(i + 2000));
+    }
+    tx.push(10).unwrap();
+    assert_eq!(rx.pop(), Some(10));
+    for i in 20..23 {
+        tx.push(i).unwrap();
+    }
+    assert!(tx.is_full());
+    assert_eq!(tx.push(23), Err(23));
+    assert_eq!(rx.peek(), Some(&20));
+    assert_eq!(rx.len(), 3);
+    drop(tx);
+    drop(rx);
+
+    assert_eq!(vec, [1, 2, 20, 21, 22]);
+
+    let mut vec = Vec::with_capacity(10);
+    vec.extend(["a", "b"].map(String::from));
+    let (mut tx, rx) = vec.split_spsc().unwrap();
+    for s in ["c", "d", "e"] {
+        tx.push(s.into()).unwrap();
+    }
+    drop(rx);
+    drop(tx);
+    assert_eq!(vec, ["a", "b", "c", "d", "e"]);
+}
+
+#[test]
+fn spsc_empty_test() {
+    let mut vec = vec![1, 2];
+    vec.shrink_to_fit();
+    assert!(vec.split_spsc().is_none());
+    assert_eq!(vec, [1, 2]);
+
+    let mut vec = vec![(); 2];
+    assert!(vec.split_spsc().is_none());
+
+    let mut vec = Vec::<u64>::with_capacity(size_of::<usize>());
+    let (mut tx, mut rx) = vec.split_spsc().unwrap();
+    assert_eq!(tx.capacity(), 0);
+    assert_eq!(tx.push(3), Err(3));
+    assert_eq!(rx.pop(), None);
+    drop((tx, rx));
+    assert_eq!(vec, []);
+}
+
+#[test]
+fn spsc_thread_test() {
+    let mut storage = ArrayVecStorage::<Box<usize>, 16>::new();
+    let (mut tx, mut rx) = storage.split_spsc().unwrap();
+    assert!(tx.capacity() >= 5);
+
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..10_000 {
+                let mut value = Box::new(i);
+                while let Err(v) = tx.push(value) {
+                    value = v;
+                    std::thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < 9_995 {
+            match rx.pop() {
+                Some(n) => {
+                    assert_eq!(*n, expected);
+                    expected += 1;
+                },
+                None => std::thread::yield_now(),
+            }
+        }
+    });
+    drop(rx);
+
+    assert_eq!(storage.as_slice(), (9_995..10_000).map(Box::new).collect::<Vec<_>>());
+}
+
+#[test]
+fn tail_ring_test() {
+    let mut vec = vec![String::from("header")];
+    vec.reserve_exact(4);
+    let (_, rest) = vec.split_tail(1);
+    let mut ring = rest.into_ring(OverflowPolicy::Overwrite);
+    assert_eq!(ring.capacity(), 4);
+
+    for i in 0..10 {
+        ring.push(i.to_string()).unwrap();
+    }
+    assert!(ring.is_full());
+    assert_eq!(ring.iter().collect::<Vec<_>>(), ["6", "7", "8", "9"]);
+    assert_eq!(ring.get(0).unwrap(), "6");
+    assert_eq!(ring.get(4), None);
+    assert_eq!(ring.pop_front().unwrap(), "6");
+    assert_eq!(ring.pop_back().unwrap(), "9");
+    ring.get_mut(0).unwrap().push('!');
+    ring.push("10".into()).unwrap();
+    assert_eq!(format!("{ring:?}"), r#"["7!", "8", "10"]"#);
+    for s in &mut ring {
+        s.push('?');
+    }
+
+    let mut rest = ring.into_tail();
+    assert_eq!(rest, ["7!?", "8?", "10?"]);
+    rest.push("11".into()).unwrap();
+    let mut ring = rest.into_ring(OverflowPolicy::Reject);
+    assert_eq!(ring.push("12".into()), Err("12".into()));
+    ring.clear();
+    assert!(ring.is_empty());
+    ring.push("13".into()).unwrap();
+    drop(ring);
+
+    assert_eq!(vec, ["header", "13"]);
+}
+
+#[test]
+fn tail_ring_callback_test() {
+    let mut evicted = Vec::new();
+    let mut on_evict = |s: String| evicted.push(s);
+
+    let mut vec = vec![String::new(); 2];
+    vec.reserve_exact(1);
+    let (_, rest) = vec.split_tail(0);
+    let mut ring = rest.into_ring(OverflowPolicy::Callback(&mut on_evict));
+    ring.extend(["a", "b", "c", "d", "e"].map(String::from));
+    assert_eq!(ring.as_slices(), (&["c".to_owned(), "d".to_owned()][..], &["e".to_owned()][..]));
+    drop(ring);
+    assert_eq!(vec, ["c", "d", "e"]);
+    assert_eq!(evicted, ["", "", "a", "b"]);
+
+    let mut vec: Vec<i32> = vec![];
+    let (_, rest) = vec.split_tail(0);
+    let mut count = 0;
+    let mut on_evict = |_| count += 1;
+    let mut ring = rest.into_ring(OverflowPolicy::Callback(&mut on_evict));
+    ring.push(1).unwrap();
+    assert_eq!(ring.as_slices(), (&[][..], &[][..]));
+    drop(ring);
+    assert_eq!(count, 1);
+
+    let mut vec = vec![(); 3];
+    let (_, rest) = vec.split_tail(1);
+    let mut ring = rest.into_ring(OverflowPolicy::Overwrite);
+    ring.extend([(); 5]);
+    assert_eq!(ring.len(), 7);
+    assert_eq!(ring.pop_front(), Some(()));
+    drop(ring);
+    assert_eq!(vec.len(), 7);
+}
+
+#[test]
+fn tail_deque_test() {
+    let mut vec = vec![String::from("a")];
+    vec.reserve_exact(4);
+    let (_, rest) = vec.split_tail(1);
+    let mut deque = rest.into_deque();
+    assert_eq!(deque.capacity(), 4);
+
+    deque.push_front("c".into()).unwrap();
+    deque.push_front("b".into()).unwrap();
+    deque.push_back("d".into()).unwrap();
+    deque.push_back("e".into()).unwrap();
+    assert_eq!(deque, ["b", "c", "d", "e"]);
+    assert_eq!(deque.push_back("f".into()), Err("f".into()));
+    assert_eq!(deque.push_front("f".into()), Err("f".into()));
+
+    assert_eq!(deque.pop_front().unwrap(), "b");
+    assert_eq!(deque.pop_back().unwrap(), "e");
+    deque.push_back("f".into()).unwrap();
+    deque.push_back("g".into()).unwrap();
+    assert_eq!(deque, ["c", "d", "f", "g"]);
+    deque[0].push('!');
+
+    let mut rest = deque.into_tail();
+    assert_eq!(rest, ["c!", "d", "f", "g"]);
+    rest.truncate(2);
+    let mut deque = rest.into_deque();
+    assert_eq!(deque.pop_front().unwrap(), "c!");
+    deque.clear();
+    assert!(deque.is_empty());
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+    deque.extend(["h".into(), "i".into()]);
+    assert_eq!(format!("{deque:?}"), r#"["h", "i"]"#);
+    drop(deque);
+
+    assert_eq!(vec, ["a", "h", "i"]);
+}
+
+#[test]
+fn tail_deque_queue_test() {
+    let mut vec: Vec<usize> = Vec::with_capacity(17);
+    vec.push(usize::MAX);
+    let (_, rest) = vec.split_tail(1);
+    let mut queue = rest.into_deque();
+
+    queue.push_back(0).unwrap();
+    let mut next = 1;
+    for expected in 0..1000 {
+        assert_eq!(queue.pop_front(), Some(expected));
+        while queue.len() < 8 {
+            queue.push_back(next).unwrap();
+            next += 1;
+        }
+    }
+    for i in 0..4 {
+        queue.push_front(usize::MAX - 1 - i).unwrap();
+    }
+    assert_eq!(queue.len(), 12);
+    assert_eq!(queue[4], 1000);
+    drop(queue);
+    assert_eq!(vec.len(), 13);
+    assert_eq!(vec[..2], [usize::MAX, usize::MAX - 4]);
+    assert_eq!(vec[5..], [1000, 1001, 1002, 1003, 1004, 1005, 1006, 1007]);
+}
+
+#[test]
+fn tail_deque_edit_test() {
+    let mut vec = vec![String::from("x")];
+    vec.reserve_exact(6);
+    let (_, rest) = vec.split_tail(1);
+    let mut deque = rest.into_deque();
+    assert_eq!(deque.capacity(), 6);
+
+    // wrap the elements around the end of capacity
+    deque.extend(["a", "b", "c", "d"].map(String::from));
+    assert_eq!(deque.pop_front().as_deref(), Some("a"));
+    assert_eq!(deque.pop_front().as_deref(), Some("b"));
+    deque.push_back("e".into()).unwrap();
+    deque.push_back("f".into()).unwrap();
+    deque.push_back("g".into()).unwrap();
+    assert_eq!(deque.as_slices().0, ["c", "d", "e", "f"]);
+    assert_eq!(deque.as_slices().1, ["g"]);
+
+    deque.insert(1, "1".into()).unwrap();
+    assert_eq!(deque, ["c", "1", "d", "e", "f", "g"]);
+    assert_eq!(deque.insert(0, "full".into()), Err("full".into()));
+    assert_eq!(deque.remove(4), "f");
+    assert_eq!(deque.remove(0), "c");
+    deque.insert(3, "2".into()).unwrap();
+    assert_eq!(deque, ["1", "d", "e", "2", "g"]);
+    assert_eq!(deque.iter().map(String::as_str).collect::<String>(), "1de2g");
+    for s in deque.iter_mut() {
+        s.push('!');
+    }
+
+    deque.retain(|s| s != "e!");
+    assert_eq!(deque, ["1!", "d!", "2!", "g!"]);
+    deque.push_front("0".into()).unwrap();
+    let drained = deque.drain(1..3).collect::<Vec<_>>();
+    assert_eq!(drained, ["1!", "d!"]);
+    assert_eq!(deque, ["0", "2!", "g!"]);
+    assert_eq!(deque.get(2).map(String::as_str), Some("g!"));
+    assert_eq!(deque.get(3), None);
+    drop(deque);
+    assert_eq!(vec, ["x", "0", "2!", "g!"]);
+}
+
+#[test]
+fn tail_deque_resize_test() {
+    let mut vec = vec![String::from("x")];
+    vec.reserve_exact(5);
+    let (_, rest) = vec.split_tail(1);
+    let mut deque = rest.into_deque();
+
+    // wrap the elements around the end of capacity
+    deque.extend(["a", "b", "c"].map(String::from));
+    assert_eq!(deque.pop_front().as_deref(), Some("a"));
+    assert_eq!(deque.pop_front().as_deref(), Some("b"));
+    assert_eq!(deque.resize(6, "-".into()), Err("-".into()));
+    deque.resize(5, "-".into()).unwrap();
+    assert_eq!(deque, ["c", "-", "-", "-", "-"]);
+    assert!(!deque.as_slices().1.is_empty());
+
+    deque[2] = "d".into();
+    deque[4] = "e".into();
+    assert_eq!(deque.swap_remove_back(1).as_deref(), Some("-"));
+    assert_eq!(deque, ["c", "e", "d", "-"]);
+    assert_eq!(deque.swap_remove_front(2).as_deref(), Some("d"));
+    assert_eq!(deque, ["e", "c", "-"]);
+    assert_eq!(deque.swap_remove_back(3), None);
+    assert_eq!(deque.swap_remove_front(3), None);
+    assert_eq!(deque.swap_remove_back(2).as_deref(), Some("-"));
+    assert_eq!(deque.swap_remove_front(0).as_deref(), Some("e"));
+    assert_eq!(deque, ["c"]);
+
+    let mut i = 0;
+    assert!(deque.resize_with(6, String::new).is_err());
+    assert!(deque.resize_with(4, || { i += 1; i.to_string() }).is_ok());
+    assert_eq!(deque, ["c", "1", "2", "3"]);
+    for s in &mut deque {
+        s.push('!');
+    }
+    assert_eq!((&deque).into_iter().count(), 4);
+    deque.truncate(5);
+    assert_eq!(deque.len(), 4);
+    deque.truncate(2);
+    assert_eq!(deque, ["c!", "1!"]);
+    assert!(deque.resize_with(1, String::new).is_ok());
+    assert_eq!(deque, ["c!"]);
+    deque.push_front("0".into()).unwrap();
+    drop(deque);
+    assert_eq!(vec, ["x", "0", "c!"]);
+}
+
+#[test]
+fn tail_heap_test() {
+    let mut vec = vec![u32::MAX];
+    vec.reserve_exact(64);
+    let (_, rest) = vec.split_tail(1);
+    let mut heap = rest.into_heap();
+    assert_eq!(heap.peek(), None);
+    assert!(heap.peek_mut().is_none());
+    assert_eq!(heap.pop(), None);
+
+    let values = (0..64u32).map(|i| i.wrapping_mul(2654435761) % 1000).collect::<Vec<_>>();
+    heap.extend(values.iter().copied());
+    assert_eq!(heap.push(0), Err(0));
+    assert_eq!(heap.len(), 64);
+
+    let mut sorted = values.clone();
+    sorted.sort_unstable();
+    assert_eq!(heap.peek(), sorted.last());
+
+    *heap.peek_mut().unwrap() = 0;
+    sorted.pop();
+    sorted.insert(0, 0);
+    assert_eq!(heap.peek(), sorted.last());
+    assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), sorted.pop().unwrap());
+
+    let top = heap.drain_sorted().take(3).collect::<Vec<_>>();
+    assert_eq!(top, sorted.iter().rev().take(3).copied().collect::<Vec<_>>());
+    assert!(heap.is_empty());
+
+    heap.extend(values.iter().copied());
+    assert_eq!(heap.pop(), values.iter().max().copied());
+    let tail = heap.into_sorted();
+    let mut expected = values.clone();
+    expected.sort_unstable();
+    expected.pop();
+    assert_eq!(tail, expected[..]);
+    drop(tail);
+    assert_eq!(vec[0], u32::MAX);
+    assert_eq!(vec[1..], expected);
+}
+
+#[test]
+fn tail_heap_drop_test() {
+    let mut vec = ["c", "a", "d", "b"].map(String::from).to_vec();
+    let (_, rest) = vec.split_tail(1);
+    let mut heap = rest.into_heap();
+    assert_eq!(heap.peek().unwrap(), "d");
+    heap.pop();
+    assert_eq!(format!("{heap:?}"), r#"["b", "a"]"#);
+    drop(heap);
+    assert_eq!(vec, ["c", "b", "a"]);
+
+    let mut vec = vec![1, 2, 3];
+    let (_, rest) = vec.split_tail(0);
+    let rest = rest.into_heap().into_tail();
+    assert_eq!(rest, [3, 2, 1]);
+}
+
+#[test]
+fn stable_sort_by_test() {
+    use crate::utils::{merge_in_place, stable_sort_by};
+
+    for len in [0, 1, 7, 8, 9, 33, 100] {
+        let mut data = (0..len)
+            .map(|i: u32| (i.wrapping_mul(2654435761) % 7, i))
+            .collect::<Vec<_>>();
+        let mut expected = data.clone();
+        expected.sort_by_key(|&(k, _)| k);
+        stable_sort_by(&mut data, &mut |a, b| a.0.cmp(&b.0));
+        assert_eq!(data, expected);
+    }
+
+    let mut data = [(1, 'a'), (3, 'b'), (3, 'c'), (0, 'd'), (3, 'e'), (4, 'f')];
+    merge_in_place(&mut data, 3, &mut |a, b| a.0.cmp(&b.0));
+    assert_eq!(data, [(0, 'd'), (1, 'a'), (3, 'b'), (3, 'c'), (3, 'e'), (4, 'f')]);
+}
+
+#[test]
+fn sorted_tail_vec_test() {
+    let mut vec = vec!["z".to_owned()];
+    vec.reserve_exact(8);
+    let (_, rest) = vec.split_tail(1);
+    let mut sorted = rest.into_sorted_tail_by_key(|s: &String| s.len());
+
+    for s in ["ccc", "a", "bb", "dd", "e"] {
+        sorted.insert_sorted(s.into()).unwrap();
+    }
+    assert_eq!(sorted, ["a", "e", "bb", "dd", "ccc"]);
+    assert!(matches!(sorted.insert_unique("xx".into()), Ok(2 | 3)));
+    assert_eq!(sorted.len(), 5);
+    assert!(sorted.contains(&"??".into()));
+    assert!(!sorted.contains(&"????".into()));
+    assert_eq!(sorted.range("_".to_owned()..="__".to_owned()), ["a", "e", "bb", "dd"]);
+
+    assert_eq!(sorted.merge_from(["ffff", "g", "hh"].map(String::from)), Ok(()));
+    assert_eq!(sorted, ["a", "e", "g", "bb", "dd", "hh", "ccc", "ffff"]);
+    assert_eq!(sorted.insert_sorted("i".into()), Err("i".into()));
+    assert!(matches!(sorted.insert_unique("j".into()), Ok(0..=2)));
+    assert_eq!(sorted.remove_value(&"___".into()).unwrap(), "ccc");
+    assert_eq!(sorted.remove_value(&"___".into()), None);
+    assert_eq!(sorted.pop().unwrap(), "ffff");
+    assert_eq!(sorted.remove(0), "a");
+    assert_eq!(format!("{sorted:?}"), r#"["e", "g", "bb", "dd", "hh"]"#);
+    drop(sorted);
+
+    assert_eq!(vec, ["z", "e", "g", "bb", "dd", "hh"]);
+}
+
+#[test]
+fn sorted_tail_vec_merge_overflow_test() {
+    let mut vec = vec![5, 1];
+    vec.reserve_exact(2);
+    let (_, rest) = vec.split_tail(0);
+    let mut sorted = rest.into_sorted_tail();
+    assert_eq!(sorted, [1, 5]);
+
+    let mut iter = [4, 0, 3, 2].into_iter();
+    assert_eq!(sorted.merge_from(&mut iter), Err(3));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(sorted, [0, 1, 4, 5]);
+    assert_eq!(sorted.into_tail(), [0, 1, 4, 5]);
+}
+
+#[test]
+fn tail_slab_test() {
+    let mut vec = vec![String::from("a"), String::from("b")];
+    vec.reserve_exact(6);
+    let (_, rest) = vec.split_tail(0);
+    let Ok(mut slab) = rest.into_slab() else { panic!() };
+    assert_eq!(slab.len(), 2);
+    assert_eq!(slab.capacity(), 7);
+    assert_eq!(slab.get(1).map(String::as_str), Some("b"));
+
+    assert_eq!(slab.remove(0).as_deref(), Some("a"));
+    assert_eq!(slab.remove(0), None);
+    assert!(!slab.contains(0));
+    assert_eq!(slab.insert("c".into()), Ok(0));
+    assert_eq!(slab.insert("d".into()), Ok(2));
+
+    let addr: *const String = slab.get(2).unwrap();
+    assert_eq!(slab.remove(1).as_deref(), Some("b"));
+    assert!(std::ptr::eq(addr, slab.get(2).unwrap()));
+
+    for (_, value) in &mut slab {
+        value.push('!');
+    }
+    assert_eq!(slab.iter().len(), 2);
+    assert_eq!(format!("{slab:?}"), r#"{0: "c!", 2: "d!"}"#);
+
+    while slab.insert("e".into()).is_ok() {}
+    assert_eq!(slab.len(), slab.capacity());
+    assert_eq!(slab.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+               (0..slab.capacity()).collect::<Vec<_>>());
+    slab.clear();
+    assert!(slab.is_empty());
+    assert_eq!(slab.insert("f".into()), Ok(0));
+    drop(slab);
+    assert_eq!(vec, ["f"]);
+}
+
+#[test]
+fn tail_slab_compact_test() {
+    let mut vec = vec![0u64; 3];
+    vec.reserve_exact(13);
+    let (_, rest) = vec.split_tail(3);
+    let Ok(mut slab) = rest.into_slab() else { panic!() };
+    assert_eq!(slab.capacity(), 12);
+    let keys = (1..=8).map(|i| slab.insert(i).unwrap()).collect::<Vec<_>>();
+    assert_eq!(keys, (0..8).collect::<Vec<_>>());
+    slab.remove(0);
+    slab.remove(5);
+
+    let mut moves = vec![];
+    let mut rest = slab.compact(|old, new| moves.push((old, new)));
+    assert_eq!(rest, [2, 3, 4, 5, 7, 8]);
+    assert_eq!(moves, [(1, 0), (2, 1), (3, 2), (4, 3), (6, 4), (7, 5)]);
+    rest.push(9).unwrap();
+    drop(rest);
+    assert_eq!(vec, [0, 0, 0, 2, 3, 4, 5, 7, 8, 9]);
+
+    // the most recently vacated slot is reused first
+    let mut vec = vec![1u64; 4];
+    vec.reserve_exact(1);
+    let (_, rest) = vec.split_tail(0);
+    let Ok(mut slab) = rest.into_slab() else { panic!() };
+    assert_eq!(slab.capacity(), 4);
+    assert_eq!(slab.insert(2), Err(2));
+    assert_eq!(slab.remove(2), Some(1));
+    assert_eq!(slab.remove(2), None);
+    assert_eq!(slab.remove(0), Some(1));
+    assert_eq!(format!("{slab:?}"), "{1: 1, 3: 1}");
+    assert_eq!(slab.insert(3), Ok(0));
+    assert_eq!(slab.insert(4), Ok(2));
+    drop(slab);
+    assert_eq!(vec, [3, 1, 4, 1]);
+
+    // the lowest vacated slot is reused first, when `T` is smaller than `usize`
+    let mut vec = vec![0u8; 3];
+    vec.reserve_exact(13);
+    let (_, rest) = vec.split_tail(3);
+    let Ok(mut slab) = rest.into_slab() else { panic!() };
+    assert_eq!(slab.capacity(), 11);
+    for i in 1..=8 {
+        slab.insert(i).unwrap();
+    }
+    assert_eq!(slab.remove(5), Some(6));
+    assert_eq!(slab.remove(1), Some(2));
+    assert_eq!(slab.remove(1), None);
+    assert!(!slab.contains(1));
+    assert!(slab.contains(2));
+    assert_eq!(slab.insert(10), Ok(1));
+    assert_eq!(slab.insert(11), Ok(5));
+    assert_eq!(slab.insert(12), Ok(8));
+    assert_eq!(slab.get(8), Some(&12));
+    drop(slab);
+    assert_eq!(vec, [0, 0, 0, 1, 10, 3, 4, 5, 11, 7, 8, 12]);
+
+    let mut vec = vec![1u8; 4];
+    let (_, rest) = vec.split_tail(0);
+    assert!(rest.into_slab().is_err());
+    let mut vec = vec![(); 4];
+    let (_, rest) = vec.split_tail(0);
+    assert!(rest.into_slab().is_err());
+}
+
+#[test]
+fn tail_slab_compact_panic_test() {
+    let mut vec = vec![String::from("head")];
+    vec.reserve_exact(6);
+    let (_, rest) = vec.split_tail(1);
+    let Ok(mut slab) = rest.into_slab() else { panic!() };
+    for s in ["a", "b", "c", "d", "e"] {
+        slab.insert(s.into()).unwrap();
+    }
+    slab.remove(0);
+    slab.remove(2);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        drop(slab.compact(|old, _| assert_ne!(old, 3)));
+    }));
+    assert!(result.is_err());
+    assert_eq!(vec, ["head", "b", "d"]);
+}
+
+#[test]
+fn arena_tail_test() {
+    let mut vec = vec![String::from("a")];
+    vec.reserve_exact(3);
+    let (_, rest) = vec.split_tail(0);
+    let mut arena = rest.into_arena();
+    assert_eq!(arena.len(), 1);
+    assert_eq!(arena.capacity(), 4);
+
+    let b = arena.alloc("b".into()).unwrap();
+    let addr: *const String = b;
+    let cd = arena.alloc_extend(["c".into(), "d".into()]).unwrap();
+    b.push_str(&cd[0]);
+    cd[1].push_str(b);
+    assert!(arena.alloc_extend(Some("e".into())).is_err());
+    assert!(std::ptr::eq(addr, &arena.as_slice_mut()[1]));
+    assert_eq!(arena.as_slice_mut(), ["a", "bc", "c", "dbc"]);
+
+    let mut rest = arena.into_tail();
+    assert_eq!(rest.pop().as_deref(), Some("dbc"));
+    drop(rest);
+    assert_eq!(vec, ["a", "bc", "c"]);
+}
+
+#[test]
+fn arena_tail_reentry_test() {
+    let mut vec = Vec::with_capacity(4);
+    let (_, rest) = vec.split_tail(0);
+    let arena = rest.into_arena();
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        arena.alloc_extend((0..2).map(|i| *arena.alloc(i + 10).unwrap()))
+            .map(|values| values.len())
+    }));
+    assert!(res.is_err());
+    assert_eq!(arena.len(), 1);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let last = std::iter::from_fn(|| {
+            arena.alloc(30).unwrap();
+            None
+        });
+        arena.alloc_extend(std::iter::once(20).chain(last))
+            .map(|values| values.len())
+    }));
+    assert!(res.is_err());
+    drop(arena);
+    assert_eq!(vec, [10, 20, 30]);
+}
+
+#[test]
+fn pinned_tail_test() {
+    use std::{cell::RefCell, pin::Pin};
+
+    struct Probe<'l>(i32, &'l RefCell<Vec<(i32, usize)>>);
+    impl Drop for Probe<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push((self.0, self as *const Self as usize));
+        }
+    }
+
+    let log = RefCell::new(vec![]);
+    let mut vec = vec![Probe(0, &log)];
+    vec.reserve_exact(3);
+    let (_, mut rest) = vec.split_tail(0);
+
+    let addrs = rest.with_pinned(|pinned| {
+        assert_eq!(pinned.capacity(), 3);
+        pinned.extend([Probe(1, &log), Probe(2, &log)]);
+        let last = pinned.push_pinned(Probe(3, &log));
+        let Ok(last) = last else { panic!() };
+        assert_eq!(last.0, 3);
+        assert!(pinned.push_pinned(Probe(4, &log)).is_err());
+
+        let mut pin = pinned.get_pin_mut(1).unwrap();
+        pin.set(Probe(5, &log));
+        for probe in &mut *pinned {
+            unsafe { Pin::get_unchecked_mut(probe).0 += 10 }
+        }
+        assert_eq!(pinned.iter().map(|probe| probe.0).collect::<Vec<_>>(), [11, 15, 13]);
+        pinned.iter().map(|probe| probe as *const Probe as usize).collect::<Vec<_>>()
+    });
+    assert_eq!(rest.len(), 1);
+    drop(rest);
+    assert_eq!(vec.len(), 1);
+
+    // 4 rejected, 2 replaced, then dropped in place from front to back
+    let log = log.take();
+    assert_eq!(log.iter().map(|&(value, _)| value).collect::<Vec<_>>(), [4, 2, 11, 15, 13]);
+    assert_eq!(log[2..].iter().map(|&(_, addr)| addr).collect::<Vec<_>>(), addrs);
+}
+
+#[test]
+fn pinned_tail_panic_test() {
+    let mut vec = vec![String::from("a")];
+    vec.reserve_exact(2);
+    let (_, mut rest) = vec.split_tail(1);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        rest.with_pinned(|pinned| {
+            pinned.extend([String::from("b"), String::from("c")]);
+            pinned.extend([String::from("d")]);
+        });
+    }));
+    assert!(result.is_err());
+    assert!(rest.is_empty());
+    drop(rest);
+    assert_eq!(vec, ["a"]);
+}
+
+#[test]
+fn rewrite_test() {
+    let mut vec = vec![String::from("pre"), "a b".into(), "".into(), "c".into()];
+    vec.reserve_exact(3);
+    let (_, mut rest) = vec.split_tail(1);
+    let res = rest.rewrite(|item, out| {
+        assert!(out.remaining() <= 3);
+        item.split_whitespace().try_for_each(|word| out.emit(word.to_uppercase()))
+    });
+    assert_eq!(res, Ok(()));
+    assert_eq!(rest, ["A", "B", "C"]);
+
+    let res = rest.flat_map_in_place(|item| [item.clone(), item.to_lowercase()]);
+    assert_eq!(res, Err("b".into()));
+    assert_eq!(rest, ["A", "B", "C"]);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        let _ = rest.flat_map_in_place(|item| {
+            assert_ne!(item, "B");
+            Some(item.repeat(2))
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(rest, ["A", "B", "C"]);
+
+    assert_eq!(rest.flat_map_in_place(|_| None), Ok(()));
+    assert!(rest.is_empty());
+    assert_eq!(rest.flat_map_in_place(|_| None), Ok(()));
+    drop(rest);
+    assert_eq!(vec, ["pre"]);
+}
+
+#[test]
+fn sort_stable_in_spare_test() {
+    let mut seed = 7u32;
+    let mut rand = move || {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        seed >> 16
+    };
+    for len in [0, 1, 7, 8, 9, 33, 100, 257] {
+        for spare in [0, 1, 5, len / 4, len / 2] {
+            let data = (0..len).map(|i| (rand() % 10, i)).collect::<Vec<_>>();
+            let mut expected = data.clone();
+            expected.sort_by_key(|&(k, _)| k);
+
+            let mut vec = data.clone();
+            vec.reserve_exact(spare);
+            let (_, mut rest) = vec.split_tail(0);
+            rest.sort_by_key_in_spare(|&(k, _)| k);
+            assert_eq!(rest, *expected, "len {len} spare {spare}");
+
+            let mut vec = data;
+            vec.reserve_exact(spare);
+            let (_, mut rest) = vec.split_tail(0);
+            let res = rest.try_sort_stable_by_in_spare(|a, b| a.0.cmp(&b.0));
+            assert_eq!(res.is_ok(), rest.capacity() - rest.len() >= len / 2);
+            if res.is_ok() {
+                assert_eq!(rest, *expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn sort_stable_in_spare_panic_test() {
+    let mut vec = (0..40).rev().map(|i| i.to_string()).collect::<Vec<_>>();
+    vec.reserve_exact(20);
+    let (_, mut rest) = vec.split_tail(0);
+    let mut count = 0;
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        rest.sort_stable_by_in_spare(|a, b| {
+            count += 1;
+            assert!(count < 160);
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        });
+    }));
+    assert!(res.is_err());
+    drop(rest);
+
+    let mut sorted = vec.clone();
+    sorted.sort_by_key(|s| s.parse::<i32>().unwrap());
+    let expected = (0..40).map(|i| i.to_string()).collect::<Vec<_>>();
+    assert_eq!(sorted, expected);
+}
+
+#[test]
+fn merge_into_head_test() {
+    let mut vec = (0..20).map(|i| (i / 2 * 2, "head")).collect::<Vec<_>>();
+    vec.reserve_exact(30);
+    let (head, mut rest) = vec.split_tail(20);
+    (&mut rest).extend((0..20).rev().map(|i| (i, "tail")));
+    assert_eq!(rest.merge_into_head_by_key(head, |&(k, _)| k), 40);
+    assert!(vec.is_sorted_by_key(|&(k, _)| k));
+    assert_eq!(vec[..4], [(0, "head"), (0, "head"), (0, "tail"), (1, "tail")]);
+
+    let mut vec = vec![1, 1, 5];
+    vec.reserve_exact(5);
+    let (head, mut rest) = vec.split_tail(3);
+    (&mut rest).extend([5, 2, 2, 6, 1]);
+    assert_eq!(rest.merge_into_head_dedup_by(head, i32::cmp), 5);
+    assert_eq!(vec, [1, 1, 2, 5, 6]);
+
+    let mut vec = vec![1, 1, 5];
+    vec.reserve_exact(5);
+    let (head, mut rest) = vec.split_tail(3);
+    (&mut rest).extend([5, 2, 2, 6, 1]);
+    assert_eq!(rest.merge_into_head_dedup(head), 5);
+    assert_eq!(vec, [1, 1, 2, 5, 6]);
+
+    let mut vec = vec![(1, "head"), (5, "head")];
+    vec.reserve_exact(4);
+    let (head, mut rest) = vec.split_tail(2);
+    (&mut rest).extend([(5, "tail"), (2, "tail"), (2, "dup"), (0, "tail")]);
+    assert_eq!(rest.merge_into_head_dedup_by_key(head, |&(k, _)| k), 4);
+    assert_eq!(vec, [(0, "tail"), (1, "head"), (2, "tail"), (5, "head")]);
+
+    // Merge in place, buffer the tail, buffer the head
+    for (head_len, spare) in [(9, 0), (9, 7), (2, 2)] {
+        let tail = (0..7).map(|i| (i * 3 % 5, i)).collect::<Vec<_>>();
+        let mut vec = Vec::with_capacity(head_len + tail.len() + spare);
+        vec.extend((0..head_len).map(|i| (i / 3, 10 + i)));
+        let mut expected = [&vec[..], &tail].concat();
+        expected.sort_by_key(|&(k, _)| k);
+
+        let (head, mut rest) = vec.split_tail(head_len);
+        (&mut rest).extend(tail);
+        assert_eq!(rest.merge_into_head_by_key(head, |&(k, _)| k), expected.len());
+        assert_eq!(vec, expected);
+    }
+
+    let mut vec = vec![String::from("a")];
+    let (head, rest) = vec.split_tail(1);
+    assert_eq!(rest.merge_into_head(head), 1);
+    let mut vec: Vec<i32> = vec![];
+    let (head, rest) = vec.split_tail(0);
+    assert_eq!(rest.merge_into_head(head), 0);
+}
+
+#[test]
+fn merge_into_head_mismatch_test() {
+    let mut other = vec![1];
+    let mut vec = vec![0, 2];
+    let (_, rest) = vec.split_tail(1);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        rest.merge_into_head(&mut other);
+    }));
+    assert!(res.is_err());
+    assert_eq!(vec, [0, 2]);
+}
+
+#[test]
+fn with_scratch_test() {
+    let mut vec = vec![String::from("a")];
+    let res = vec.with_scratch(3, |mut scratch| {
+        assert!(scratch.capacity() >= 3);
+        (&mut scratch).extend(["b".into(), "c".into()]);
+        scratch.len()
+    });
+    assert_eq!(res, 2);
+    assert_eq!(vec, ["a"]);
+    assert!(vec.capacity() >= 4);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.with_scratch(1, |mut scratch| {
+            scratch.push("b".into()).unwrap();
+            panic!();
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(vec, ["a"]);
+
+    vec.with_scratch(2, |mut scratch| {
+        scratch.push(String::new()).unwrap(); // Leaked without allocation
+        forget(scratch);
+    });
+    assert_eq!(vec, ["a"]);
+}
+
+#[test]
+fn scratch_pool_test() {
+    let pool = ScratchPool::default();
+    pool.recycle(Vec::with_capacity(100));
+    pool.recycle(Vec::with_capacity(10));
+
+    let cap = pool.with_scratch(5, |scratch| scratch.capacity());
+    assert!((10..100).contains(&cap));
+    let cap = pool.with_scratch(50, |scratch| scratch.capacity());
+    assert!(cap >= 100);
+    let cap = pool.with_scratch(500, |scratch| scratch.capacity());
+    assert!(cap >= 500);
+    assert_eq!(pool.pooled(), 2);
+
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        pool.with_scratch(1, |mut scratch| {
+            scratch.push(String::from("x")).unwrap();
+            pool.with_scratch(1, |_| panic!());
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(pool.pooled(), 2);
+    assert_eq!(format!("{pool:?}"), "ScratchPool { pooled: 2 }");
+    pool.clear();
+    assert_eq!(pool.pooled(), 0);
+}
+
@@ -1,4 +1,9 @@
-use core::ops::{Bound, Range, RangeBounds, RangeTo};
+use core::{
+    cmp::Ordering,
+    mem::MaybeUninit,
+    ops::{Bound, Range, RangeBounds, RangeTo},
+    ptr,
+};
 
 #[inline]
 #[track_caller]
@@ -39,3 +44,274 @@ where R: RangeBounds<usize>,
 
     Range { start, end }
 }
+
+/// Sequence stored in two regions, `head[..mid]` then `tail[..len - mid]`
+///
+/// Each element is accessed through the pointer of its own region,
+/// so the regions can come from different borrows.
+pub struct SplitSlice<T> {
+    head: *mut T,
+    mid: usize,
+    tail: *mut T,
+    len: usize,
+}
+impl<T> Clone for SplitSlice<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for SplitSlice<T> {
+}
+impl<T> From<&mut [T]> for SplitSlice<T> {
+    fn from(v: &mut [T]) -> Self {
+        let head = v.as_mut_ptr();
+        Self { head, mid: v.len(), tail: head.wrapping_add(v.len()), len: v.len() }
+    }
+}
+impl<T> SplitSlice<T> {
+    /// Join `head` and `tail` as one sequence
+    ///
+    /// # Safety
+    /// - `tail` is valid for reads and writes of `tail_len` elements,
+    ///   and does not overlap `head`
+    pub unsafe fn new(head: &mut [T], tail: *mut T, tail_len: usize) -> Self {
+        Self { head: head.as_mut_ptr(), mid: head.len(), tail, len: head.len() + tail_len }
+    }
+
+    fn at(self, i: usize) -> *mut T {
+        debug_assert!(i < self.len);
+        if i < self.mid {
+            self.head.wrapping_add(i)
+        } else {
+            self.tail.wrapping_add(i - self.mid)
+        }
+    }
+
+    unsafe fn get<'a>(self, i: usize) -> &'a T {
+        unsafe { &*self.at(i) }
+    }
+
+    unsafe fn swap(self, a: usize, b: usize) {
+        unsafe { ptr::swap_nonoverlapping(self.at(a), self.at(b), 1) }
+    }
+
+    unsafe fn reverse(self, mut lo: usize, mut hi: usize) {
+        while lo + 1 < hi {
+            hi -= 1;
+            unsafe { self.swap(lo, hi) }
+            lo += 1;
+        }
+    }
+
+    /// Rotate `[lo, hi)` to make `mid` the first
+    unsafe fn rotate(self, lo: usize, mid: usize, hi: usize) {
+        unsafe {
+            self.reverse(lo, mid);
+            self.reverse(mid, hi);
+            self.reverse(lo, hi);
+        }
+    }
+
+    /// Copy `[start, start + len)` to `dst`
+    unsafe fn copy_to(self, start: usize, len: usize, dst: *mut T) {
+        for i in 0..len {
+            unsafe { ptr::copy_nonoverlapping(self.at(start + i), dst.add(i), 1) }
+        }
+    }
+
+    /// Copy `len` elements from `src` into `[start, start + len)`
+    unsafe fn copy_from(self, src: *const T, start: usize, len: usize) {
+        for i in 0..len {
+            unsafe { ptr::copy_nonoverlapping(src.add(i), self.at(start + i), 1) }
+        }
+    }
+}
+
+/// Stable merge of sorted `v[..mid]` and `v[mid..]` in place, no buffer is required
+///
+/// It is the SymMerge algorithm, elements are moved by swaps,
+/// it is `O(n log n)` time, equal elements of the left run stay before the right run.
+pub fn merge_in_place<T, F>(v: &mut [T], mid: usize, cmp: &mut F)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    let len = v.len();
+    if 0 < mid && mid < len {
+        unsafe { sym_merge(SplitSlice::from(v), 0, mid, len, cmp) }
+    }
+}
+
+/// SymMerge of sorted `[a, m)` and `[m, b)`, requires `a < m < b`
+unsafe fn sym_merge<T, F>(v: SplitSlice<T>, a: usize, m: usize, b: usize, cmp: &mut F)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    let less = |cmp: &mut F, i, j| unsafe { cmp(v.get(i), v.get(j)) } == Ordering::Less;
+
+    if m - a == 1 {
+        // insert v[a] before the first element not less than it
+        let (mut i, mut j) = (m, b);
+        while i < j {
+            let h = i + (j - i) / 2;
+            if less(cmp, h, a) { i = h + 1 } else { j = h }
+        }
+        for k in a..i - 1 {
+            unsafe { v.swap(k, k + 1) }
+        }
+        return;
+    }
+    if b - m == 1 {
+        // insert v[m] after the last element not greater than it
+        let (mut i, mut j) = (a, m);
+        while i < j {
+            let h = i + (j - i) / 2;
+            if less(cmp, m, h) { j = h } else { i = h + 1 }
+        }
+        for k in (i + 1..=m).rev() {
+            unsafe { v.swap(k, k - 1) }
+        }
+        return;
+    }
+
+    let mid = a + (b - a) / 2;
+    let n = mid + m;
+    let (mut start, mut r) = if m > mid { (n - b, mid) } else { (a, m) };
+    let p = n - 1;
+    while start < r {
+        let c = start + (r - start) / 2;
+        if less(cmp, p - c, c) { r = c } else { start = c + 1 }
+    }
+    let end = n - start;
+    unsafe {
+        if start < m && m < end {
+            v.rotate(start, m, end);
+        }
+        if a < start && start < mid {
+            sym_merge(v, a, start, mid, cmp);
+        }
+        if mid < end && end < b {
+            sym_merge(v, mid, end, b, cmp);
+        }
+    }
+}
+
+/// Move `len` elements from `src` into `dest..` of `v` when dropped,
+/// fill the gap of buffered merge on completion or panic
+struct MergeHole<T> {
+    v: SplitSlice<T>,
+    src: *const T,
+    len: usize,
+    dest: usize,
+}
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        unsafe { self.v.copy_from(self.src, self.dest, self.len) }
+    }
+}
+
+/// Stable merge of sorted `v[..mid]` and `v[mid..]`,
+/// the shorter run is moved into `buf` if it fits,
+/// otherwise fallback to [`merge_in_place`]
+pub fn merge_with_buf<T, F>(
+    v: &mut [T],
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    cmp: &mut F,
+)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    unsafe { merge_split_with_buf(SplitSlice::from(v), mid, buf, cmp) }
+}
+
+/// Like [`merge_with_buf`], but merge a [`SplitSlice`]
+///
+/// # Safety
+/// - `v` is valid for reads and writes, and does not overlap `buf`
+pub unsafe fn merge_split_with_buf<T, F>(
+    v: SplitSlice<T>,
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    cmp: &mut F,
+)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    let len = v.len;
+    let right_len = len - mid;
+    if mid == 0 || right_len == 0 {
+        return;
+    }
+    if mid.min(right_len) > buf.len() {
+        return unsafe { sym_merge(v, 0, mid, len, cmp) };
+    }
+    let buf = buf.as_mut_ptr().cast::<T>();
+
+    unsafe {
+        if mid <= right_len {
+            v.copy_to(0, mid, buf);
+            // gap is `v[hole.dest..hole.dest + hole.len]`
+            let mut hole = MergeHole { v, src: buf, len: mid, dest: 0 };
+            let mut right = mid;
+            while hole.len != 0 && right < len {
+                if cmp(v.get(right), &*hole.src) == Ordering::Less {
+                    ptr::copy_nonoverlapping(v.at(right), v.at(hole.dest), 1);
+                    right += 1;
+                } else {
+                    ptr::copy_nonoverlapping(hole.src, v.at(hole.dest), 1);
+                    hole.src = hole.src.add(1);
+                    hole.len -= 1;
+                }
+                hole.dest += 1;
+            }
+        } else {
+            v.copy_to(mid, right_len, buf);
+            // gap is `v[left..left + hole.len]`, `hole.dest` is `left`
+            let mut hole = MergeHole { v, src: buf, len: right_len, dest: mid };
+            while hole.len != 0 && hole.dest != 0 {
+                let last = hole.dest - 1;
+                let out = v.at(hole.dest + hole.len - 1);
+                if cmp(&*buf.add(hole.len - 1), v.get(last)) == Ordering::Less {
+                    ptr::copy_nonoverlapping(v.at(last), out, 1);
+                    hole.dest -= 1;
+                } else {
+                    ptr::copy_nonoverlapping(buf.add(hole.len - 1), out, 1);
+                    hole.len -= 1;
+                }
+            }
+        }
+    }
+}
+
+/// Stable sort in place, no buffer is required, it is `O(n log² n)` time
+pub fn stable_sort_by<T, F>(v: &mut [T], cmp: &mut F)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    stable_sort_by_buf(v, &mut [], cmp);
+}
+
+/// Stable merge sort, merge runs by `buf` if it fits, otherwise in place
+///
+/// `buf` of `v.len() / 2` is always enough
+pub fn stable_sort_by_buf<T, F>(v: &mut [T], buf: &mut [MaybeUninit<T>], cmp: &mut F)
+where F: FnMut(&T, &T) -> Ordering,
+{
+    const RUN: usize = 8;
+
+    for chunk in v.chunks_mut(RUN) {
+        for i in 1..chunk.len() {
+            let mut j = i;
+            while j > 0 && cmp(&chunk[j - 1], &chunk[j]) == Ordering::Greater {
+                chunk.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+
+    let mut width = RUN;
+    while width < v.len() {
+        for start in (0..v.len()).step_by(2 * width) {
+            let end = v.len().min(start + 2 * width);
+            if start + width < end {
+                merge_with_buf(&mut v[start..end], width, buf, cmp);
+            }
+        }
+        width *= 2;
+    }
+}